@@ -0,0 +1,57 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fibonacci::*;
+use halo2_proofs::{
+    pasta::Fp,
+    plonk::{keygen_vk, Circuit},
+};
+use std::marker::PhantomData;
+
+// (k, sequence lengths that fit in 2^k rows)
+const CASES: [(u32, &[usize]); 3] = [(4, &[5, 10]), (8, &[10, 100, 250]), (12, &[10, 1000, 4000])];
+
+fn circuit(n: usize) -> FiboCircuit<Fp> {
+    FiboCircuit {
+        a: Some(Fp::one()),
+        b: Some(Fp::one()),
+        n,
+        output: None,
+        planner: PhantomData,
+    }
+}
+
+fn bench_prove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prove");
+    group.sample_size(10);
+    for (k, lengths) in CASES {
+        // params are generated once, so only keygen + proving is measured
+        let params = params_for(k);
+        for &n in lengths {
+            // expected n-th term, used as the public input
+            let output = nth_fibonacci(n, Fp::one(), Fp::one());
+            group.bench_with_input(BenchmarkId::new(format!("k{k}"), n), &n, |b, &n| {
+                b.iter(|| prove_fibonacci_with_params(&params, circuit(n), &[output]).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify");
+    group.sample_size(10);
+    for (k, lengths) in CASES {
+        let params = params_for(k);
+        for &n in lengths {
+            let output = nth_fibonacci(n, Fp::one(), Fp::one());
+            let vk = keygen_vk(&params, &circuit(n).without_witnesses()).unwrap();
+            let proof = prove_fibonacci_with_params(&params, circuit(n), &[output]).unwrap();
+            group.bench_with_input(BenchmarkId::new(format!("k{k}"), n), &n, |b, _| {
+                b.iter(|| verify_fibonacci_with_params(&params, &vk, &[&[output]], &proof).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_prove, bench_verify);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fibonacci::*;
+use halo2_proofs::pasta::Fp;
+
+// witness generation only, no circuit
+fn bench_witness(c: &mut Criterion) {
+    let mut group = c.benchmark_group("witness");
+    for n in [1 << 12, 1 << 16, 1 << 20] {
+        group.bench_with_input(BenchmarkId::new("serial", n), &n, |b, &n| {
+            b.iter(|| fibonacci_terms(Fp::one(), Fp::one(), n))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", n), &n, |b, &n| {
+            b.iter(|| fibonacci_terms_parallel(Fp::one(), Fp::one(), n))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_witness);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+#![no_main]
+
+use fibonacci::{params_for, FiboCircuit, FiboProof};
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::{keygen_vk, VerifyingKey},
+};
+use libfuzzer_sys::fuzz_target;
+use std::sync::OnceLock;
+
+const K: u32 = 4;
+
+// keygen is slow, do it once for the whole run
+fn vk() -> &'static VerifyingKey<EqAffine> {
+    static VK: OnceLock<VerifyingKey<EqAffine>> = OnceLock::new();
+    VK.get_or_init(|| keygen_vk(&params_for(K), &FiboCircuit::<Fp>::empty(10)).unwrap())
+}
+
+// any blob must give Ok or Err, a panic is a bug
+fuzz_target!(|data: &[u8]| {
+    if let Ok(proof) = FiboProof::from_bytes(data) {
+        let _ = proof.verify(K, vk());
+    }
+});
@@ -0,0 +1,176 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::{ACell, FiboChip, FiboConfig};
+
+/// `FiboConfig` with a running sum of the terms next to the sequence
+#[derive(Clone, Debug)]
+pub struct FiboAccConfig {
+    pub fibo: FiboConfig,
+    pub acc: Column<Advice>,
+    /// first row, where the sum starts from a + b + c
+    pub acc_first: Selector,
+    pub acc_next: Selector,
+}
+
+/// `FiboChip::assign_sequence_single_region`, plus the sum of all terms
+pub struct FiboAccChip<F: Field> {
+    config: FiboAccConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> FiboAccChip<F> {
+    pub fn construct(config: FiboAccConfig) -> Self {
+        FiboAccChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> FiboAccConfig {
+        let fibo = FiboChip::configure_standalone(meta);
+        let [col_a, col_b, col_c] = fibo.advice;
+        let acc = meta.advice_column();
+        let acc_first = meta.selector();
+        let acc_next = meta.selector();
+
+        meta.enable_equality(acc);
+
+        meta.create_gate("acc first", |meta| {
+            // | col_a | col_b | col_c |     acc     | acc_first |
+            // |   a   |   b   |   c   | a + b + c   |     s     |
+            let s = meta.query_selector(acc_first);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            vec![s * (a + b + c - acc)]
+        });
+
+        meta.create_gate("acc next", |meta| {
+            // | col_c |    acc    | acc_next |
+            // |       |    acc    |          |
+            // |   c'  | acc + c'  |     s    |
+            let s = meta.query_selector(acc_next);
+            let c = meta.query_advice(col_c, Rotation::cur());
+            let acc_prev = meta.query_advice(acc, Rotation::prev());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            vec![s * (acc_prev + c - acc)]
+        });
+
+        FiboAccConfig {
+            fibo,
+            acc,
+            acc_first,
+            acc_next,
+        }
+    }
+
+    /// assign the sequence up to the `n`-th term and its running sum, binding the sum of
+    /// all `n` terms to `instance[instance_row]`, returning the term cells and the sum cell
+    /// note: the first row already sums three terms, so `n` must be at least 3
+    pub fn assign_with_accumulator(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+        n: usize,
+        instance_row: usize,
+    ) -> Result<(Vec<ACell<F>>, ACell<F>), Error> {
+        if n < 3 {
+            return Err(Error::Synthesis);
+        }
+        let fibo_chip = FiboChip::construct(self.config.fibo.clone());
+
+        let (cells, acc) = layouter.assign_region(
+            || "sequence with sum",
+            |mut region| {
+                let cells = fibo_chip.assign_sequence_in_region(&mut region, a, b, n)?;
+
+                // row r holds term r + 3 in col_c
+                self.config.acc_first.enable(&mut region, 0)?;
+                let mut sum = cells[..3]
+                    .iter()
+                    .fold(Value::known(F::ZERO), |sum, cell| sum + cell.value());
+                let mut acc = region.assign_advice(|| "acc", self.config.acc, 0, || sum)?;
+                for (row, c) in cells[3..].iter().enumerate().map(|(i, c)| (i + 1, c)) {
+                    self.config.acc_next.enable(&mut region, row)?;
+                    sum = sum + c.value();
+                    acc = region.assign_advice(|| "acc", self.config.acc, row, || sum)?;
+                }
+                Ok((cells, ACell(acc)))
+            },
+        )?;
+
+        layouter.constrain_instance(acc.cell(), self.config.fibo.instance, instance_row)?;
+        Ok((cells, acc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{
+        dev::{MockProver, VerifyFailure},
+        pasta::Fp,
+    };
+
+    // sum of the first `n` terms from (1, 1) at `instance[0]`
+    struct FiboSumCircuit(usize);
+
+    impl Circuit<Fp> for FiboSumCircuit {
+        type Config = FiboAccConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            FiboSumCircuit(self.0)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboAccChip::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboAccChip::construct(config);
+            let (cells, _) = chip.assign_with_accumulator(
+                layouter.namespace(|| "sequence"),
+                Some(Fp::one()),
+                Some(Fp::one()),
+                self.0,
+                0,
+            )?;
+            assert_eq!(cells.len(), self.0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fibo_sum_of_first_10() {
+        // 1 + 1 + 2 + 3 + 5 + 8 + 13 + 21 + 34 + 55
+        MockProver::run(4, &FiboSumCircuit(10), vec![vec![Fp::from(143)]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_sum_wrong_accumulator_fails() {
+        // sum without the last term
+        let prover = MockProver::run(4, &FiboSumCircuit(10), vec![vec![Fp::from(88)]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures
+            .iter()
+            .all(|f| matches!(f, VerifyFailure::Permutation { .. })));
+    }
+
+    #[test]
+    fn fibo_sum_needs_3_terms() {
+        assert!(matches!(
+            MockProver::run(4, &FiboSumCircuit(2), vec![vec![Fp::from(2)]]),
+            Err(Error::Synthesis)
+        ));
+    }
+}
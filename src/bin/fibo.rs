@@ -0,0 +1,100 @@
+use clap::{Parser, Subcommand};
+use fibonacci::*;
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::{keygen_vk, Circuit},
+    poly::commitment::Params,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// prove and verify the `n`-th fibonacci term
+#[derive(Parser, Debug)]
+#[command(name = "fibo")]
+struct Cli {
+    /// params are cached here, one file per k
+    /// note: IPA has no vk serialization, the vk is rebuilt from n on every run
+    #[arg(long, global = true, default_value = ".fibo-cache")]
+    cache_dir: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// write a `FiboProofBundle` of the `n`-th term of the sequence from (a, b)
+    Prove {
+        #[arg(long, default_value = "1", value_parser = parse_field::<Fp>)]
+        a: Fp,
+        #[arg(long, default_value = "1", value_parser = parse_field::<Fp>)]
+        b: Fp,
+        #[arg(long)]
+        n: usize,
+        #[arg(long, default_value = "proof.bin")]
+        out: PathBuf,
+    },
+    /// exits with 1 unless the proof shows the `n`-th term is `expected`
+    Verify {
+        #[arg(long, default_value = "proof.bin")]
+        proof: PathBuf,
+        #[arg(long, value_parser = parse_field::<Fp>)]
+        expected: Fp,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), FiboError> {
+    match cli.command {
+        Command::Prove { a, b, n, out } => {
+            let circuit = FiboCircuit::builder().seed(a, b).length(n).build()?;
+            let params = cached_params(&cli.cache_dir, auto_k(n))?;
+            let public_inputs = vec![nth_fibonacci(n, a, b)];
+            let proof = prove_fibonacci_with_params(&params, circuit, &public_inputs)?;
+
+            let bundle = FiboProofBundle {
+                n,
+                proof: FiboProof {
+                    public_inputs,
+                    proof,
+                },
+            };
+            fs::write(&out, bundle.to_bytes())?;
+            println!("wrote proof of the {n}-th term to {}", out.display());
+        }
+        Command::Verify { proof, expected } => {
+            let FiboProofBundle { n, proof } = FiboProofBundle::from_bytes(&fs::read(proof)?)?;
+            // the file can claim any n, don't generate params beyond `MAX_K` for it
+            if n > MAX_N {
+                return Err(FiboError::InvalidLength {
+                    expected: MAX_N,
+                    got: n,
+                });
+            }
+            let params = cached_params(&cli.cache_dir, auto_k(n))?;
+            let circuit = FiboCircuit::<Fp>::empty(n);
+            let vk = keygen_vk(&params, &circuit.without_witnesses())?;
+            // against `expected`, not the inputs in the file
+            verify_fibonacci_with_params(&params, &vk, &[&[expected]], &proof.proof)?;
+            println!("ok, the {n}-th term is {}", to_decimal(&expected));
+        }
+    }
+    Ok(())
+}
+
+fn cached_params(dir: &Path, k: u32) -> Result<Params<EqAffine>, FiboError> {
+    fs::create_dir_all(dir)?;
+    load_or_generate_params(k, dir.join(format!("params-k{k}.bin")))
+}
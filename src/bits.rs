@@ -0,0 +1,193 @@
+use halo2_proofs::{circuit::*, pasta::group::ff::PrimeField, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// config of the bit decomposition, one row per bit, most significant bit first
+#[derive(Clone, Debug)]
+pub struct BitDecompConfig {
+    pub value: Column<Advice>,
+    pub bit: Column<Advice>,
+    pub acc: Column<Advice>,
+    pub s_bit: Selector,
+    pub s_first: Selector,
+    pub s_step: Selector,
+    pub s_last: Selector,
+}
+
+/// splits a value into `BITS` boolean cells
+pub struct BitDecompChip<F: PrimeField, const BITS: usize> {
+    config: BitDecompConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: PrimeField, const BITS: usize> BitDecompChip<F, BITS> {
+    pub fn construct(config: BitDecompConfig) -> Self {
+        BitDecompChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> BitDecompConfig {
+        let value = meta.advice_column();
+        let bit = meta.advice_column();
+        let acc = meta.advice_column();
+        let s_bit = meta.selector();
+        let s_first = meta.selector();
+        let s_step = meta.selector();
+        let s_last = meta.selector();
+
+        meta.enable_equality(value);
+        meta.enable_equality(bit);
+
+        // | value | bit | acc         | s_bit | s_first | s_step | s_last |
+        // |       | b0  | b0          |   1   |    1    |    0   |    0   |
+        // |       | b1  | 2 * b0 + b1 |   1   |    0    |    1   |    0   |
+        // | ...   | ... | ...         |  ...  |   ...   |   ...  |   ...  |
+        // | v     | bn  | v           |   1   |    0    |    1   |    1   |
+        meta.create_gate("bool", |meta| {
+            let s = meta.query_selector(s_bit);
+            let b = meta.query_advice(bit, Rotation::cur());
+            vec![s * b.clone() * (b - Expression::Constant(F::ONE))]
+        });
+
+        meta.create_gate("first bit", |meta| {
+            let s = meta.query_selector(s_first);
+            let b = meta.query_advice(bit, Rotation::cur());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            vec![s * (acc - b)]
+        });
+
+        // note: kept apart from "first bit", a gate queries all its cells on every enabled row
+        // and the first row has no previous acc
+        meta.create_gate("running sum", |meta| {
+            let s = meta.query_selector(s_step);
+            let b = meta.query_advice(bit, Rotation::cur());
+            let acc_prev = meta.query_advice(acc, Rotation::prev());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            vec![s * (acc - acc_prev * Expression::Constant(F::from(2)) - b)]
+        });
+
+        meta.create_gate("recompose", |meta| {
+            let s = meta.query_selector(s_last);
+            let value = meta.query_advice(value, Rotation::cur());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            vec![s * (acc - value)]
+        });
+
+        BitDecompConfig {
+            value,
+            bit,
+            acc,
+            s_bit,
+            s_first,
+            s_step,
+            s_last,
+        }
+    }
+
+    /// returns the bit cells, least significant bit first
+    pub fn decompose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Value<F>,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        layouter.assign_region(
+            || "bit decomposition",
+            |mut region| {
+                let mut bits = Vec::with_capacity(BITS);
+                let mut acc = Value::known(F::ZERO);
+
+                // note: bits past BITS are dropped, a value that doesn't fit fails the recompose gate
+                for row in 0..BITS {
+                    let i = BITS - 1 - row;
+                    self.config.s_bit.enable(&mut region, row)?;
+                    if row == 0 {
+                        self.config.s_first.enable(&mut region, row)?;
+                    } else {
+                        self.config.s_step.enable(&mut region, row)?;
+                    }
+
+                    let bit = value
+                        .map(|v| F::from(((v.to_repr().as_ref()[i / 8] >> (i % 8)) & 1) as u64));
+                    acc = acc.zip(bit).map(|(acc, bit)| acc.double() + bit);
+
+                    bits.push(
+                        region
+                            .assign_advice(|| "bit", self.config.bit, row, || bit)
+                            .map(ACell)?,
+                    );
+                    region.assign_advice(|| "acc", self.config.acc, row, || acc)?;
+                }
+
+                self.config.s_last.enable(&mut region, BITS - 1)?;
+                region.assign_advice(|| "value", self.config.value, BITS - 1, || value)?;
+
+                bits.reverse();
+                Ok(bits)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    struct BitDecompCircuit<const BITS: usize> {
+        value: Value<Fp>,
+        expected: Vec<u64>,
+    }
+
+    impl<const BITS: usize> Circuit<Fp> for BitDecompCircuit<BITS> {
+        type Config = BitDecompConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                value: Value::unknown(),
+                expected: self.expected.clone(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            BitDecompChip::<Fp, BITS>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = BitDecompChip::<Fp, BITS>::construct(config);
+            let bits = chip.decompose(layouter.namespace(|| "value"), self.value)?;
+            for (bit, expected) in bits.iter().zip(&self.expected) {
+                bit.value().assert_if_known(|b| **b == Fp::from(*expected));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn bit_decomp_13() {
+        let circuit = BitDecompCircuit::<4> {
+            value: Value::known(Fp::from(13)),
+            expected: vec![1, 0, 1, 1],
+        };
+        MockProver::run(4, &circuit, vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn bit_decomp_value_too_large_fails() {
+        let circuit = BitDecompCircuit::<4> {
+            value: Value::known(Fp::from(16)),
+            expected: vec![],
+        };
+        let prover = MockProver::run(4, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,662 @@
+use halo2_proofs::{
+    circuit::{floor_planner::V1, Layouter, SimpleFloorPlanner, Value},
+    dev::{CircuitCost, CircuitGates, MockProver},
+    pasta::{group::prime::PrimeGroup, Eq, Fp},
+    plonk::{
+        Advice, Any, Assigned, Assignment, Circuit, Column, ConstraintSystem, Error, Fixed,
+        FloorPlanner, Instance, Selector,
+    },
+};
+use std::{fmt::Debug, marker::PhantomData};
+
+use crate::{
+    build_cs, column_index, nth_fibonacci, prove_fibonacci, selector_index, FiboCircuit, FiboError,
+};
+
+/// measure the cost of the fibonacci circuit computing the `n`-th term
+/// note: panics if `k` is too small for `n`, same as `CircuitCost::measure`
+pub fn fibonacci_cost(k: u32, n: usize) -> CircuitCost<Eq, FiboCircuit<Fp>> {
+    let circuit = FiboCircuit::<Fp>::empty(n);
+    CircuitCost::measure(k, &circuit)
+}
+
+/// bytes of a real proof of the `n`-th term at size `k`, seeded with (1, 1)
+pub fn proof_size(k: u32, n: usize) -> Result<usize, FiboError> {
+    let circuit = FiboCircuit::builder().length(n).build()?;
+    let output = nth_fibonacci(n, Fp::one(), Fp::one());
+    Ok(prove_fibonacci(k, circuit, &[output])?.len())
+}
+
+/// `proof_size` estimated from the circuit shape without proving: the marginal proof size
+/// of one instance, plus the fixed, vanishing and IPA parts every proof has
+/// note: the IPA part takes 2 points per `k`, so both sizes grow by 64 bytes per `k`.
+/// the estimate stays within `PROOF_SIZE_TOLERANCE` of the real proof
+pub fn expected_proof_size(k: u32, n: usize) -> usize {
+    fibonacci_cost(k, n).proof_size(1).into()
+}
+
+/// bytes `expected_proof_size` may be off from `proof_size`, two field elements
+pub const PROOF_SIZE_TOLERANCE: usize = 64;
+
+/// rows used by the circuit (max over all columns)
+pub fn cost_rows<G: PrimeGroup, C: Circuit<G::Scalar> + Debug>(cost: &CircuitCost<G, C>) -> usize {
+    debug_field(cost, "max_rows")
+}
+
+/// number of advice columns used by the circuit
+pub fn cost_advice_columns<G: PrimeGroup, C: Circuit<G::Scalar> + Debug>(
+    cost: &CircuitCost<G, C>,
+) -> usize {
+    debug_field(cost, "num_advice_columns")
+}
+
+/// number of columns of any type used by the circuit
+pub fn cost_total_columns<G: PrimeGroup, C: Circuit<G::Scalar> + Debug>(
+    cost: &CircuitCost<G, C>,
+) -> usize {
+    debug_field(cost, "num_total_columns")
+}
+
+/// number of columns with equality enabled, each chunk of them adds a permutation product
+/// commitment to the proof
+pub fn cost_permutation_columns<G: PrimeGroup, C: Circuit<G::Scalar> + Debug>(
+    cost: &CircuitCost<G, C>,
+) -> usize {
+    debug_field(cost, "permutation_cols")
+}
+
+/// max degree of the gates and lookups, each degree above 2 adds a commitment to the proof
+pub fn cost_max_degree<G: PrimeGroup, C: Circuit<G::Scalar> + Debug>(
+    cost: &CircuitCost<G, C>,
+) -> usize {
+    debug_field(cost, "max_deg")
+}
+
+/// dump rows, columns and marginal proof size of the circuit
+pub fn print_cost<G: PrimeGroup, C: Circuit<G::Scalar> + Debug>(cost: CircuitCost<G, C>) {
+    println!("rows: {}", cost_rows(&cost));
+    println!(
+        "columns: {} ({} advice)",
+        cost_total_columns(&cost),
+        cost_advice_columns(&cost)
+    );
+    println!(
+        "marginal proof size: {} bytes",
+        usize::from(cost.marginal_proof_size())
+    );
+}
+
+/// `circuit` laid out by the floor planner `P` instead of its own
+#[derive(Debug)]
+pub struct WithPlanner<C, P> {
+    pub circuit: C,
+    _planner: PhantomData<P>,
+}
+
+impl<C, P> WithPlanner<C, P> {
+    pub fn new(circuit: C) -> Self {
+        WithPlanner {
+            circuit,
+            _planner: PhantomData,
+        }
+    }
+}
+
+impl<C: Circuit<Fp>, P: FloorPlanner> Circuit<Fp> for WithPlanner<C, P> {
+    type Config = C::Config;
+    type FloorPlanner = P;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.circuit.without_witnesses())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        C::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        self.circuit.synthesize(config, layouter)
+    }
+}
+
+/// cost of `circuit` laid out by `P`
+pub type PlannerCost<C, P> = CircuitCost<Eq, WithPlanner<C, P>>;
+
+/// `MockProver::run` of `circuit` laid out by `P`
+pub fn run_with_planner<P: FloorPlanner, C: Circuit<Fp>>(
+    k: u32,
+    circuit: C,
+    instance: Vec<Vec<Fp>>,
+) -> Result<MockProver<Fp>, Error> {
+    MockProver::run(k, &WithPlanner::<C, P>::new(circuit), instance)
+}
+
+/// cost of `circuit` laid out by `SimpleFloorPlanner` and by `V1`
+/// note: `SimpleFloorPlanner` stacks regions in order, `V1` measures them first and packs
+/// them by column, so it only differs for circuits with several regions
+pub fn planner_costs<C: Circuit<Fp> + Debug>(
+    k: u32,
+    circuit: &C,
+) -> (PlannerCost<C, SimpleFloorPlanner>, PlannerCost<C, V1>) {
+    (
+        CircuitCost::measure(k, &WithPlanner::new(circuit.without_witnesses())),
+        CircuitCost::measure(k, &WithPlanner::new(circuit.without_witnesses())),
+    )
+}
+
+/// JSON description of the constraint system of the circuit computing the `n`-th term, e.g.
+/// `{"n":10,"advice_columns":3,...,"gates":[{"name":"add","polynomials":["S0 * (A0@0 + A1@0 - A2@0)"]}]}`
+/// note: selectors are counted before they are compressed into fixed columns at keygen
+pub fn describe_circuit(n: usize) -> String {
+    let meta = build_cs();
+    let pinned = meta.pinned();
+
+    // gate names and polynomials only show up in the Display output of CircuitGates:
+    // "name:" followed by one "- polynomial" line per constraint
+    let mut gates: Vec<(String, Vec<String>)> = vec![];
+    for line in CircuitGates::collect::<Fp, FiboCircuit<Fp>>()
+        .to_string()
+        .lines()
+        .take_while(|line| !line.starts_with("Total gates"))
+    {
+        match (line.strip_prefix("- "), gates.last_mut()) {
+            (Some(poly), Some((_, polys))) => polys.push(poly.to_string()),
+            _ => gates.push((line.trim_end_matches(':').to_string(), vec![])),
+        }
+    }
+
+    let gates = gates
+        .iter()
+        .map(|(name, polys)| {
+            let polys: Vec<_> = polys.iter().map(|p| json_string(p)).collect();
+            format!(
+                r#"{{"name":{},"polynomials":[{}]}}"#,
+                json_string(name),
+                polys.join(",")
+            )
+        })
+        .collect::<Vec<_>>();
+    format!(
+        r#"{{"n":{n},"advice_columns":{},"instance_columns":{},"fixed_columns":{},"selectors":{},"gates":[{}]}}"#,
+        debug_field(&pinned, "num_advice_columns"),
+        debug_field(&pinned, "num_instance_columns"),
+        debug_field(&pinned, "num_fixed_columns"),
+        debug_field(&pinned, "num_selectors"),
+        gates.join(",")
+    )
+}
+
+fn json_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// witness table of the circuit as its floor planner assigns it, `[column][row]` of hex strings,
+/// `None` for unassigned (and blinding) rows
+/// note: instance columns are all zero, cells copied from them are dumped as zero
+pub fn dump_advice<C: Circuit<Fp>>(k: u32, circuit: &C) -> Result<Vec<Vec<Option<String>>>, Error> {
+    let cells = CellRecorder::record(k, circuit, vec![])?;
+    Ok(cells
+        .advice
+        .into_iter()
+        .map(|column| {
+            let hex = |value: Fp| format!("{value:?}");
+            column.into_iter().map(|cell| cell.map(hex)).collect()
+        })
+        .collect())
+}
+
+/// cells a circuit assigns, `[column][row]`, recorded while its floor planner synthesizes it
+/// note: like MockProver, rows past the usable ones are `NotEnoughRowsAvailable` and
+/// unknown values `Synthesis` errors
+pub(crate) struct CellRecorder {
+    k: u32,
+    usable_rows: usize,
+    instance: Vec<Vec<Fp>>,
+    pub advice: Vec<Vec<Option<Fp>>>,
+    pub fixed: Vec<Vec<Option<Fp>>>,
+    pub selectors: Vec<Vec<bool>>,
+}
+
+impl CellRecorder {
+    /// synthesize `circuit` in `2^k` rows, `query_instance` reads `instance` (zero past it)
+    pub fn record<C: Circuit<Fp>>(
+        k: u32,
+        circuit: &C,
+        instance: Vec<Vec<Fp>>,
+    ) -> Result<Self, Error> {
+        let mut meta = ConstraintSystem::default();
+        let config = C::configure(&mut meta);
+        let pinned = meta.pinned();
+        let rows = 1 << k;
+        let columns = |field| vec![vec![None; rows]; debug_field(&pinned, field)];
+
+        let mut cells = CellRecorder {
+            k,
+            usable_rows: rows.saturating_sub(meta.blinding_factors() + 1),
+            instance,
+            advice: columns("num_advice_columns"),
+            fixed: columns("num_fixed_columns"),
+            selectors: vec![vec![false; rows]; debug_field(&pinned, "num_selectors")],
+        };
+        let constants = constants_columns(&meta);
+        C::FloorPlanner::synthesize(&mut cells, circuit, config, constants)?;
+        Ok(cells)
+    }
+
+    fn check_row(&self, row: usize) -> Result<(), Error> {
+        if row >= self.usable_rows {
+            return Err(Error::NotEnoughRowsAvailable { current_k: self.k });
+        }
+        Ok(())
+    }
+}
+
+// `Value` only hands its content out to closures
+fn known(value: Value<Fp>) -> Result<Fp, Error> {
+    let mut known = None;
+    value.map(|v| known = Some(v));
+    known.ok_or(Error::Synthesis)
+}
+
+impl Assignment<Fp> for CellRecorder {
+    fn enter_region<NR: Into<String>, N: FnOnce() -> NR>(&mut self, _: N) {}
+
+    fn exit_region(&mut self) {}
+
+    fn enable_selector<A: FnOnce() -> AR, AR: Into<String>>(
+        &mut self,
+        _: A,
+        selector: &Selector,
+        row: usize,
+    ) -> Result<(), Error> {
+        self.check_row(row)?;
+        self.selectors[selector_index(selector)][row] = true;
+        Ok(())
+    }
+
+    fn query_instance(&self, column: Column<Instance>, row: usize) -> Result<Value<Fp>, Error> {
+        let rows = self.instance.get(column_index(&column));
+        let value = rows.and_then(|rows| rows.get(row)).copied();
+        Ok(Value::known(value.unwrap_or(Fp::zero())))
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        column: Column<Advice>,
+        row: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<Fp>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.check_row(row)?;
+        let value = known(to().map(|v| v.into().evaluate()))?;
+        self.advice[column_index(&column)][row] = Some(value);
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        column: Column<Fixed>,
+        row: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<Fp>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.check_row(row)?;
+        let value = known(to().map(|v| v.into().evaluate()))?;
+        self.fixed[column_index(&column)][row] = Some(value);
+        Ok(())
+    }
+
+    fn copy(&mut self, _: Column<Any>, _: usize, _: Column<Any>, _: usize) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        column: Column<Fixed>,
+        from_row: usize,
+        to: Value<Assigned<Fp>>,
+    ) -> Result<(), Error> {
+        self.check_row(from_row)?;
+        let value = known(to.map(|v| v.evaluate()))?;
+        let usable_rows = self.usable_rows;
+        self.fixed[column_index(&column)][from_row..usable_rows].fill(Some(value));
+        Ok(())
+    }
+
+    fn push_namespace<NR: Into<String>, N: FnOnce() -> NR>(&mut self, _: N) {}
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+/// two cells with the same value, as `(column, row)` with absolute rows
+pub type CopiedCells = ((Column<Any>, usize), (Column<Any>, usize));
+
+/// copy constraints of a circuit, see `describe_permutation`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PermutationReport {
+    /// `(type, index)` of the columns with `enable_equality`, constants columns included
+    pub columns: Vec<(Any, usize)>,
+    pub copies: Vec<CopiedCells>,
+}
+
+/// equality enabled columns of `C::configure`, and the cells its synthesis copies
+/// note: every copy is a cycle in the permutation argument, e.g. the per row regions of
+/// `FiboChip::assign_full_sequence` copy two cells per row where the single region layout copies none
+pub fn describe_permutation<C: Circuit<Fp>>(circuit: &C) -> Result<PermutationReport, Error> {
+    let mut meta = ConstraintSystem::default();
+    let config = C::configure(&mut meta);
+    let columns = debug_columns(&format!("{:?}", meta.pinned()), "permutation");
+    let constants = constants_columns(&meta);
+
+    let mut recorder = CopyRecorder(vec![]);
+    C::FloorPlanner::synthesize(&mut recorder, circuit, config, constants)?;
+    Ok(PermutationReport {
+        columns,
+        copies: recorder.0,
+    })
+}
+
+// fixed columns the floor planner assigns the circuit's constants into
+fn constants_columns(meta: &ConstraintSystem<Fp>) -> Vec<Column<Fixed>> {
+    // columns have no public constructor, fresh ones get the same indices in allocation order
+    let mut fixed = ConstraintSystem::<Fp>::default();
+    let fixed: Vec<_> = (0..debug_field(&meta.pinned(), "num_fixed_columns"))
+        .map(|_| fixed.fixed_column())
+        .collect();
+    debug_columns(&format!("{:?}", meta.pinned()), "constants")
+        .into_iter()
+        .map(|(_, index)| fixed[index])
+        .collect()
+}
+
+// `(type, index)` of the columns listed under `field`,
+// e.g. `permutation: Argument { columns: [Column { index: 0, column_type: Advice }, ..] }`
+fn debug_columns(debug: &str, field: &str) -> Vec<(Any, usize)> {
+    let pattern = format!(" {field}: ");
+    let start = debug
+        .find(&pattern)
+        .unwrap_or_else(|| panic!("no `{field}` field in {debug}"));
+    let list = &debug[start..];
+    let list = &list[..list.find(']').unwrap()];
+    list.split("Column { index: ")
+        .skip(1)
+        .map(|column| {
+            let (index, column_type) = column.split_once(", column_type: ").unwrap();
+            let column_type = match column_type.trim_end_matches([' ', '}', ',']) {
+                "Advice" => Any::Advice,
+                "Fixed" => Any::Fixed,
+                "Instance" => Any::Instance,
+                other => panic!("unknown column type {other}"),
+            };
+            (column_type, index.parse().unwrap())
+        })
+        .collect()
+}
+
+// synthesis backend only keeping the copies, witnesses are still computed
+struct CopyRecorder(Vec<CopiedCells>);
+
+impl Assignment<Fp> for CopyRecorder {
+    fn enter_region<NR: Into<String>, N: FnOnce() -> NR>(&mut self, _: N) {}
+
+    fn exit_region(&mut self) {}
+
+    fn enable_selector<A: FnOnce() -> AR, AR: Into<String>>(
+        &mut self,
+        _: A,
+        _: &Selector,
+        _: usize,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Value<Fp>, Error> {
+        Ok(Value::unknown())
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Advice>,
+        _: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<Fp>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        to();
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        _: Column<Fixed>,
+        _: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<Fp>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        to();
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        left_column: Column<Any>,
+        left_row: usize,
+        right_column: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        self.0
+            .push(((left_column, left_row), (right_column, right_row)));
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: Value<Assigned<Fp>>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn push_namespace<NR: Into<String>, N: FnOnce() -> NR>(&mut self, _: N) {}
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+// note: CircuitCost keeps its fields private, the Debug output is the only way to read them
+// (same for the pinned ConstraintSystem)
+fn debug_field(cost: &impl Debug, field: &str) -> usize {
+    let debug = format!("{cost:?}");
+    let pattern = format!(" {field}: ");
+    let start = debug
+        .find(&pattern)
+        .unwrap_or_else(|| panic!("no `{field}` field in {debug}"))
+        + pattern.len();
+    debug[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>()
+        .parse()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FiboConstantSeedCircuit;
+
+    #[test]
+    fn fibo_dump_advice() {
+        let circuit = FiboCircuit::<Fp> {
+            a: Some(Fp::one()),
+            b: Some(Fp::one()),
+            n: 12,
+            output: None,
+            planner: PhantomData,
+        };
+        let advice = dump_advice(4, &circuit).unwrap();
+        assert_eq!(advice.len(), 3);
+        assert!(advice.iter().all(|column| column.len() == 16));
+
+        // col_a holds the terms 1..=10, one per row
+        let expected: Vec<_> = (1..=10)
+            .map(|n| Some(format!("{:?}", nth_fibonacci(n, Fp::one(), Fp::one()))))
+            .collect();
+        assert_eq!(advice[0][..10], expected);
+        assert_eq!(advice[0][1].as_deref(), Some(&*format!("0x{:064x}", 1)));
+        assert_eq!(advice[0][10], None);
+    }
+
+    #[test]
+    fn fibo_describe_permutation() {
+        let circuit = FiboCircuit::<Fp> {
+            a: Some(Fp::one()),
+            b: Some(Fp::one()),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        let report = describe_permutation(&circuit).unwrap();
+        for index in 0..3 {
+            assert!(report.columns.contains(&(Any::Advice, index)));
+        }
+        assert!(report.columns.contains(&(Any::Instance, 0)));
+        assert!(report.columns.contains(&(Any::Fixed, 0)));
+
+        // the single region only copies the output into the instance
+        assert_eq!(report.copies.len(), 1);
+        let ((_, row), (column, instance_row)) = report.copies[0];
+        assert_eq!(
+            (row, *column.column_type(), instance_row),
+            (7, Any::Instance, 0)
+        );
+
+        // per row regions copy b, c of the previous row for the 7 rows after the first,
+        // plus the 2 constants of the seed and the output
+        let per_row = describe_permutation(&FiboConstantSeedCircuit::<Fp>::new(10)).unwrap();
+        assert_eq!(per_row.copies.len(), 2 * 7 + 2 + 1);
+    }
+
+    #[test]
+    fn fibo_floor_planners() {
+        let expected = nth_fibonacci(10, Fp::one(), Fp::one());
+        let single = || FiboCircuit::<Fp> {
+            a: Some(Fp::one()),
+            b: Some(Fp::one()),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        // one region per row
+        let per_row = || FiboConstantSeedCircuit::<Fp>::new(10);
+
+        for prover in [
+            run_with_planner::<SimpleFloorPlanner, _>(5, single(), vec![vec![expected]]),
+            run_with_planner::<V1, _>(5, single(), vec![vec![expected]]),
+            run_with_planner::<SimpleFloorPlanner, _>(5, per_row(), vec![vec![expected]]),
+            run_with_planner::<V1, _>(5, per_row(), vec![vec![expected]]),
+        ] {
+            prover.unwrap().assert_satisfied();
+        }
+
+        // rows, columns, degree and proof size of both layouts
+        fn measures<C: Circuit<Fp> + Debug, P: FloorPlanner + Debug>(
+            cost: &PlannerCost<C, P>,
+        ) -> [usize; 5] {
+            [
+                cost_rows(cost),
+                cost_advice_columns(cost),
+                cost_total_columns(cost),
+                cost_max_degree(cost),
+                cost.proof_size(1).into(),
+            ]
+        }
+
+        // nothing to pack in a single region
+        let (simple, v1) = planner_costs(5, &single());
+        assert_eq!(measures(&simple), measures(&v1));
+        assert_eq!(measures(&simple)[0], 8);
+
+        // every row region uses all three advice columns, so `V1` can't place two side by side
+        // and ends up with the same cost
+        let (simple, v1) = planner_costs(5, &per_row());
+        assert_eq!(measures(&simple), measures(&v1));
+        assert_eq!(measures(&simple)[0], 8);
+    }
+
+    #[test]
+    fn fibo_cost_rows_grow_with_n() {
+        let rows: Vec<_> = [5, 10, 20, 40]
+            .into_iter()
+            .map(|n| cost_rows(&fibonacci_cost(7, n)))
+            .collect();
+        assert!(rows.windows(2).all(|w| w[0] < w[1]), "{rows:?}");
+        // one row per term after the first row
+        assert_eq!(rows[1], 8);
+    }
+
+    #[test]
+    fn fibo_describe_circuit() {
+        let json = describe_circuit(10);
+        let desc: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(desc["advice_columns"], 3);
+        assert_eq!(desc["selectors"], 2);
+
+        let gates = desc["gates"].as_array().unwrap();
+        let names: Vec<_> = gates.iter().map(|g| g["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["add", "next row"]);
+        assert_eq!(
+            gates[0]["polynomials"],
+            serde_json::json!(["S0 * (A0@0 + A1@0 - A2@0)"])
+        );
+        assert_eq!(gates[1]["polynomials"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn fibo_cost_columns() {
+        let cost = fibonacci_cost(4, 10);
+        assert_eq!(cost_advice_columns(&cost), 3);
+        // 3 advice, 1 instance, 1 constants and 2 fixed columns for the "add" and "next row" selectors
+        assert_eq!(cost_total_columns(&cost), 7);
+    }
+
+    #[test]
+    fn fibo_proof_size() {
+        let sizes: Vec<_> = (5..8)
+            .map(|k| (proof_size(k, 10).unwrap(), expected_proof_size(k, 10)))
+            .collect();
+        for &(actual, expected) in &sizes {
+            assert!(
+                actual.abs_diff(expected) <= PROOF_SIZE_TOLERANCE,
+                "{actual} vs {expected}"
+            );
+        }
+        // one more IPA round per k
+        for pair in sizes.windows(2) {
+            assert!(pair[1].0 > pair[0].0 && pair[1].1 > pair[0].1);
+        }
+    }
+}
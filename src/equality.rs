@@ -0,0 +1,146 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::{ACell, IsZeroChip, IsZeroConfig};
+
+/// config of the equality check: `diff = a - b` in one row, then `is_zero(diff)`
+#[derive(Clone, Debug)]
+pub struct EqualityConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+    pub is_zero: IsZeroConfig,
+}
+
+/// outputs 1 if two cells are equal, 0 otherwise
+/// note: the circuit is the same whatever the values, `is_zero` takes an inverse witness
+/// instead of branching, so the proof doesn't tell which way the comparison went. the
+/// witnesses are computed in constant time too (`Field::is_zero`, not `is_zero_vartime`)
+pub struct EqualityChip<F: Field> {
+    config: EqualityConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> EqualityChip<F> {
+    pub fn construct(config: EqualityConfig) -> Self {
+        EqualityChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> EqualityConfig {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let selector = meta.selector();
+        let is_zero = IsZeroChip::configure(meta);
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("diff", |meta| {
+            // | col_a | col_b | col_diff | selector |
+            // |   a   |   b   |   a - b  |     s    |
+            let s = meta.query_selector(selector);
+            let [a, b, diff] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![s * (a - b - diff)]
+        });
+
+        EqualityConfig {
+            advice,
+            selector,
+            is_zero,
+        }
+    }
+
+    pub fn is_equal(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        let diff = layouter.assign_region(
+            || "diff",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                // copy constraint
+                let [col_a, col_b, col_diff] = self.config.advice;
+                a.as_ref().copy_advice(|| "a", &mut region, col_a, 0)?;
+                b.as_ref().copy_advice(|| "b", &mut region, col_b, 0)?;
+                let diff = a.value().copied() - b.value().copied();
+                region
+                    .assign_advice(|| "diff", col_diff, 0, || diff)
+                    .map(ACell)
+            },
+        )?;
+
+        IsZeroChip::construct(self.config.is_zero.clone())
+            .assign_cell(layouter.namespace(|| "is zero"), &diff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FiboChip, FiboConfig};
+    use halo2_proofs::{
+        dev::{MockProver, VerifyFailure},
+        pasta::Fp,
+    };
+
+    // fib(9) == fib(10) at instance row 0, fib(10) == fib(10) at row 1
+    struct EqualTermsCircuit;
+
+    impl Circuit<Fp> for EqualTermsCircuit {
+        type Config = (FiboConfig, EqualityConfig);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            (
+                FiboChip::configure_standalone(meta),
+                EqualityChip::configure(meta),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            (fibo, equality): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let fibo = FiboChip::construct(fibo);
+            let equality = EqualityChip::construct(equality);
+            let cells = fibo.assign_sequence_single_region(
+                layouter.namespace(|| "sequence"),
+                Some(Fp::one()),
+                Some(Fp::one()),
+                10,
+            )?;
+
+            let (ninth, tenth) = (&cells[8], &cells[9]);
+            let unequal = equality.is_equal(layouter.namespace(|| "9 vs 10"), ninth, tenth)?;
+            let equal = equality.is_equal(layouter.namespace(|| "10 vs 10"), tenth, tenth)?;
+            fibo.expose_public(layouter.namespace(|| "unequal"), &unequal, 0)?;
+            fibo.expose_public(layouter.namespace(|| "equal"), &equal, 1)
+        }
+    }
+
+    #[test]
+    fn equality_of_terms() {
+        MockProver::run(5, &EqualTermsCircuit, vec![vec![Fp::zero(), Fp::one()]])
+            .unwrap()
+            .assert_satisfied();
+
+        // claiming fib(9) == fib(10)
+        let failures = MockProver::run(5, &EqualTermsCircuit, vec![vec![Fp::one(), Fp::one()]])
+            .unwrap()
+            .verify()
+            .unwrap_err();
+        assert!(failures
+            .iter()
+            .all(|f| matches!(f, VerifyFailure::Permutation { .. })));
+    }
+}
@@ -0,0 +1,33 @@
+use alloc::string::String;
+use halo2_proofs::plonk;
+#[cfg(feature = "std")]
+use std::io;
+
+/// error of the public api, `synthesize` still returns `plonk::Error` as the trait demands
+#[derive(Debug, thiserror::Error)]
+pub enum FiboError {
+    /// proving needs the seed, but the circuit was built without witnesses
+    #[error("circuit has no witness, can't prove without the seed")]
+    MissingWitness,
+    #[error("synthesis error: {0}")]
+    Synthesis(#[from] plonk::Error),
+    /// errors of the KZG backend, which has its own `plonk::Error`
+    #[cfg(feature = "kzg")]
+    #[error("kzg error: {0}")]
+    Kzg(#[from] halo2_axiom::plonk::Error),
+    #[cfg(feature = "std")]
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    /// neither decimal nor `0x` hex, see `parse_field`
+    #[error("invalid field element: {0:?}")]
+    InvalidScalar(String),
+    /// a well formed number, but not below the field modulus
+    #[error("field element out of range: {value} >= {modulus}")]
+    ScalarOutOfRange { value: String, modulus: String },
+    /// first proof of `verify_batch` that doesn't verify on its own
+    #[error("proof {index} of the batch is invalid")]
+    InvalidBatchProof { index: usize },
+    /// note: for the sequence length `expected` is the bound it crossed, minimum or maximum
+    #[error("invalid length: expected {expected}, got {got}")]
+    InvalidLength { expected: usize, got: usize },
+}
@@ -0,0 +1,2666 @@
+use alloc::{format, rc::Rc, string::String, vec, vec::Vec};
+use core::{cell::RefCell, marker::PhantomData, ops::Range};
+#[cfg(feature = "std")]
+use halo2_proofs::dev::{FailureLocation, MockProver, VerifyFailure};
+use halo2_proofs::{
+    arithmetic::Field, circuit::*, pasta::group::ff::PrimeField, plonk::*, poly::Rotation,
+};
+
+use crate::FiboError;
+
+/// config of circuit defines the number of columns
+#[derive(Clone, Debug)]
+pub struct FiboConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+    /// chains a row to the next one inside a single region, see `assign_sequence_single_region`
+    pub next_selector: Selector,
+    /// constants pinned by the circuit, see `assign_first_row_constant`
+    pub constants: Column<Fixed>,
+    pub instance: Column<Instance>,
+}
+
+/// column and selector indices, e.g.
+/// `FiboConfig { advice: [0,1,2], selector: 0, next_selector: 1, constants: 0, instance: 0 }`
+impl core::fmt::Display for FiboConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let [a, b, c] = self.advice.map(|column| column_index(&column));
+        write!(
+            f,
+            "FiboConfig {{ advice: [{a},{b},{c}], selector: {}, next_selector: {}, constants: {}, instance: {} }}",
+            selector_index(&self.selector),
+            selector_index(&self.next_selector),
+            column_index(&self.constants),
+            column_index(&self.instance),
+        )
+    }
+}
+
+// note: halo2 keeps the indices `pub(crate)`, only `Debug` shows them,
+// e.g. `Column { index: 2, column_type: Advice }` and `Selector(0, true)`
+pub(crate) fn column_index<C: ColumnType>(column: &Column<C>) -> usize {
+    let debug = format!("{column:?}");
+    parse_index(&debug, "index: ")
+}
+
+pub(crate) fn selector_index(selector: &Selector) -> usize {
+    let debug = format!("{selector:?}");
+    parse_index(&debug, "Selector(")
+}
+
+fn parse_index(debug: &str, prefix: &str) -> usize {
+    debug
+        .split_once(prefix)
+        .and_then(|(_, rest)| {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            rest[..end].parse().ok()
+        })
+        .unwrap_or_else(|| panic!("no index in {debug}"))
+}
+
+// note: derived, `F: Field` is already `Clone`
+#[derive(Clone)]
+pub struct FiboChip<F: Field> {
+    config: FiboConfig,
+    _marker: PhantomData<F>,
+}
+
+// want to reuse this Assigned Cell type
+#[derive(Clone, Debug)]
+pub struct ACell<F: Field>(pub AssignedCell<F, F>);
+
+// cells of the first row: (a, b, c)
+pub type FirstRow<F> = (ACell<F>, ACell<F>, ACell<F>);
+
+// seed witnesses, both known when proving and both unknown at keygen
+// note: a single known one would only give a half known sequence, so it's rejected
+fn seed_values<F: Field>(a: Option<F>, b: Option<F>) -> Result<(Value<F>, Value<F>), Error> {
+    match (a, b) {
+        (Some(a), Some(b)) => Ok((Value::known(a), Value::known(b))),
+        (None, None) => Ok((Value::unknown(), Value::unknown())),
+        _ => Err(Error::Synthesis),
+    }
+}
+
+fn is_known<V>(value: &Value<V>) -> bool {
+    let mut known = false;
+    value.as_ref().map(|_| known = true);
+    known
+}
+
+impl<F: Field> ACell<F> {
+    /// witness of the cell, unknown during keygen
+    pub fn value(&self) -> Value<&F> {
+        self.0.value()
+    }
+
+    /// the underlying cell, for manual copy constraints
+    pub fn cell(&self) -> Cell {
+        self.0.cell()
+    }
+}
+
+// gadget APIs take the raw cell
+impl<F: Field> AsRef<AssignedCell<F, F>> for ACell<F> {
+    fn as_ref(&self) -> &AssignedCell<F, F> {
+        &self.0
+    }
+}
+
+impl<F: Field> From<AssignedCell<F, F>> for ACell<F> {
+    fn from(cell: AssignedCell<F, F>) -> Self {
+        ACell(cell)
+    }
+}
+
+impl<F: Field> FiboChip<F> {
+    /// base on config, construct the chip
+    pub fn construct(config: FiboConfig) -> Self {
+        FiboChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /// same as `construct`
+    pub fn new(config: FiboConfig) -> Self {
+        Self::construct(config)
+    }
+
+    /// rows the sequence up to the `n`-th term takes, blinding rows not included
+    /// note: the first row holds terms 1..=3 and every next row adds one term, so `n - 2` rows,
+    /// e.g. 8 for n = 10. n < 3 still assigns the whole first row
+    pub fn rows_for(n: usize) -> usize {
+        n.saturating_sub(2).max(1)
+    }
+
+    /// base on constraint system, return configure the chip
+    /// columns are passed in, so a larger circuit can share them with other chips
+    /// panics if a column is passed twice, the gate would then constrain a cell against itself
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        selector: Selector,
+    ) -> FiboConfig {
+        let [col_a, col_b, col_c] = advice;
+        assert!(
+            col_a != col_b && col_b != col_c && col_a != col_c,
+            "FiboChip needs 3 distinct advice columns, got {advice:?}"
+        );
+        let selector_column = selector;
+        let instance = meta.instance_column();
+        let constants = meta.fixed_column();
+
+        // 0. This enables permutation check
+        // need to copy the sequences to next raw (= using permutation argument)
+        meta.enable_equality(col_a);
+        meta.enable_equality(col_b);
+        meta.enable_equality(col_c);
+        // instance column also needs equality to bind the output cell to public input
+        meta.enable_equality(instance);
+        // note: enable_constant also enables equality, the constant is copied into the advice cell
+        meta.enable_constant(constants);
+
+        meta.create_gate("add", |meta| {
+            // 1. Query cells from columns
+            // | col_a | col_b | col_c | selector |
+            // |   a   |   b   |   c   |     s    |
+            let s = meta.query_selector(selector_column);
+            // note: rotation is offset of the row compared from selector location / rotation cause the cost
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+
+            // 2. Return the constraint
+            // a + b = c as a constraint in this case ( only when the selector,s, is true )
+            vec![s * (a + b - c)]
+        });
+
+        // replaces the copy constraints between rows when the whole sequence is in one region
+        let next_selector = meta.selector();
+        meta.create_gate("next row", |meta| {
+            // | col_a  | col_b  | col_c | next_selector |
+            // |   a    |   b    |   c   |       s       |
+            // | a' = b | b' = c |       |               |
+            let s = meta.query_selector(next_selector);
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+            let a_next = meta.query_advice(col_a, Rotation::next());
+            let b_next = meta.query_advice(col_b, Rotation::next());
+
+            vec![s.clone() * (a_next - b), s * (b_next - c)]
+        });
+
+        FiboConfig {
+            advice: [col_a, col_b, col_c],
+            selector: selector_column,
+            next_selector,
+            constants,
+            instance,
+        }
+    }
+
+    /// `configure` on freshly allocated columns
+    pub fn configure_standalone(meta: &mut ConstraintSystem<F>) -> FiboConfig {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let selector = meta.selector();
+        Self::configure(meta, advice, selector)
+    }
+
+    /// assign the first row at `offset` of the region, so several sequences can share a region
+    /// note: a and b are unknown when synthesizing without witnesses (e.g. keygen),
+    /// only one of them known is a `Error::Synthesis`
+    pub fn assign_first_row(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        a_value: Value<F>,
+        b_value: Value<F>,
+    ) -> Result<FirstRow<F>, Error> {
+        if is_known(&a_value) != is_known(&b_value) {
+            return Err(Error::Synthesis);
+        }
+
+        // 1. Enable the selector (= I will enable constraint of the first row)
+        self.config.selector.enable(region, offset)?;
+
+        // 2. assign the two private value a,b to the cell
+        let a_cell = region
+            .assign_advice(|| "a", self.config.advice[0], offset, || a_value)
+            .map(ACell)?;
+
+        let b_cell = region
+            .assign_advice(|| "b", self.config.advice[1], offset, || b_value)
+            .map(ACell)?;
+
+        // c value is a + b
+        let c_val = a_value.zip(b_value).map(|(a, b)| a + b);
+        let c_cell = region
+            .assign_advice(|| "c", self.config.advice[2], offset, || c_val)
+            .map(ACell)?;
+
+        Ok((a_cell, b_cell, c_cell))
+    }
+
+    /// `assign_first_row` of witnesses given as `Option`, `None` being unknown
+    #[deprecated(note = "use `assign_first_row` with `Value::known` or `Value::unknown`")]
+    pub fn assign_first_row_option(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<FirstRow<F>, Error> {
+        let (a, b) = seed_values(a, b)?;
+        self.assign_first_row(region, offset, a, b)
+    }
+
+    /// same as `assign_first_row`, but a and b are copied from `instance[0]` and `instance[1]`
+    pub fn assign_first_row_from_instance(
+        &self,
+        mut layouter: impl Layouter<F>,
+        instance: Column<Instance>,
+    ) -> Result<FirstRow<F>, Error> {
+        layouter.assign_region(
+            || "first row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                // copy constraint with the public seed
+                let a_cell = region
+                    .assign_advice_from_instance(|| "a", instance, 0, self.config.advice[0], 0)
+                    .map(ACell)?;
+                let b_cell = region
+                    .assign_advice_from_instance(|| "b", instance, 1, self.config.advice[1], 0)
+                    .map(ACell)?;
+
+                let c_val = a_cell.value().zip(b_cell.value()).map(|(a, b)| *a + *b);
+                let c_cell = region
+                    .assign_advice(|| "c", self.config.advice[2], 0, || c_val)
+                    .map(ACell)?;
+
+                Ok((a_cell, b_cell, c_cell))
+            },
+        )
+    }
+
+    /// same as `assign_first_row`, but a and b are constants fixed at keygen,
+    /// so the prover can't pick another seed
+    pub fn assign_first_row_constant(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: F,
+        b: F,
+    ) -> Result<FirstRow<F>, Error> {
+        layouter.assign_region(
+            || "first row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                // copy constraint with the constants column
+                let a_cell = region
+                    .assign_advice_from_constant(|| "a", self.config.advice[0], 0, a)
+                    .map(ACell)?;
+                let b_cell = region
+                    .assign_advice_from_constant(|| "b", self.config.advice[1], 0, b)
+                    .map(ACell)?;
+
+                let c_cell = region
+                    .assign_advice(|| "c", self.config.advice[2], 0, || Value::known(a + b))
+                    .map(ACell)?;
+
+                Ok((a_cell, b_cell, c_cell))
+            },
+        )
+    }
+
+    /// assign the row after `prev_b`, `prev_c` at `offset` of the region, returning the c cell
+    /// note: the previous cells are copied in, they can live in this region or another one
+    pub fn assign_row(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        prev_b: &ACell<F>,
+        prev_c: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        self.assign_row_with(region, offset, prev_b, prev_c, |b, c| *b + *c)
+    }
+
+    /// same as `assign_row`, with the c value computed by `f(prev_b, prev_c)`
+    /// note: the gate still checks `a + b = c`, it is the source of truth, so a proof
+    /// with any other `f` fails
+    pub fn assign_row_with<G: Fn(&F, &F) -> F>(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        prev_b: &ACell<F>,
+        prev_c: &ACell<F>,
+        f: G,
+    ) -> Result<ACell<F>, Error> {
+        self.config.selector.enable(region, offset)?;
+
+        // copy constraint
+        prev_b
+            .as_ref()
+            .copy_advice(|| "a", region, self.config.advice[0], offset)?;
+        prev_c
+            .as_ref()
+            .copy_advice(|| "b", region, self.config.advice[1], offset)?;
+
+        let c_val = prev_b.value().zip(prev_c.value()).map(|(b, c)| f(b, c));
+        region
+            .assign_advice(|| "c", self.config.advice[2], offset, || c_val)
+            .map(ACell)
+    }
+
+    /// assign the whole sequence up to the `n`-th term, returning the cells of all terms in order
+    pub fn assign_full_sequence(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+        n: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let (a_cell, b_cell, c_cell) = layouter.assign_region(
+            || "first row",
+            |mut region| {
+                let (a, b) = seed_values(a, b)?;
+                self.assign_first_row(&mut region, 0, a, b)
+            },
+        )?;
+
+        // first row already computed the 3rd term
+        let rest = self.assign_rows(layouter, &b_cell, &c_cell, n.saturating_sub(3))?;
+        let mut cells = vec![a_cell, b_cell, c_cell];
+        cells.extend(rest);
+        // the first row always holds 3 terms, drop the 3rd one when n = 2
+        cells.truncate(n);
+        Ok(cells)
+    }
+
+    /// assign `count` rows after the row ending with `first_b`, `first_c`,
+    /// returning the new c cells in order
+    /// note: regions are named "row 1", "row 2", .. after the first row, MockProver failures
+    /// and the layout plot show these, namespaces are dropped by both
+    pub fn assign_rows(
+        &self,
+        mut layouter: impl Layouter<F>,
+        first_b: &ACell<F>,
+        first_c: &ACell<F>,
+        count: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let mut cells = Vec::with_capacity(count);
+        let (mut prev_b, mut prev_c) = (first_b.clone(), first_c.clone());
+        for i in 1..=count {
+            let c = layouter.assign_region(
+                || format!("row {i}"),
+                |mut region| self.assign_row(&mut region, 0, &prev_b, &prev_c),
+            )?;
+            cells.push(c.clone());
+            (prev_b, prev_c) = (prev_c, c);
+        }
+        Ok(cells)
+    }
+
+    /// same as `assign_full_sequence`, but all rows go into one region
+    /// note: rows are chained by the "next row" gate instead of `copy_advice`,
+    /// so the only copy constraint left is the public output
+    pub fn assign_sequence_single_region(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+        n: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        layouter.assign_region(
+            || "sequence",
+            |mut region| self.assign_sequence_in_region(&mut region, a, b, n),
+        )
+    }
+
+    /// rows of `assign_sequence_single_region` from offset 0 of the given region,
+    /// so another chip can add columns to the same rows
+    pub fn assign_sequence_in_region(
+        &self,
+        region: &mut Region<'_, F>,
+        a: Option<F>,
+        b: Option<F>,
+        n: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        // first row holds terms 1..=3, every next row adds one
+        let rows = n.saturating_sub(2).max(1);
+        let (mut a_val, mut b_val) = seed_values(a, b)?;
+        let mut cells = Vec::with_capacity(n);
+
+        self.enable_selector_rows(region, 0..rows)?;
+        for row in 0..rows {
+            if row + 1 < rows {
+                self.config.next_selector.enable(region, row)?;
+            }
+
+            let c_val = a_val + b_val;
+            let a_cell = region.assign_advice(|| "a", self.config.advice[0], row, || a_val)?;
+            let b_cell = region.assign_advice(|| "b", self.config.advice[1], row, || b_val)?;
+            let c_cell = region.assign_advice(|| "c", self.config.advice[2], row, || c_val)?;
+
+            if row == 0 {
+                cells.push(ACell(a_cell));
+                cells.push(ACell(b_cell));
+            }
+            cells.push(ACell(c_cell));
+            (a_val, b_val) = (b_val, c_val);
+        }
+
+        cells.truncate(n);
+        Ok(cells)
+    }
+
+    /// `assign_sequence_in_region` with the witness precomputed, e.g. by `fibonacci_terms_parallel`,
+    /// `None` without witnesses. needs the `rows_for(n) + 2` terms of the filled rows
+    pub fn assign_terms_in_region(
+        &self,
+        region: &mut Region<'_, F>,
+        terms: Option<&[F]>,
+        n: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let rows = Self::rows_for(n);
+        if terms.is_some_and(|terms| terms.len() < rows + 2) {
+            return Err(Error::Synthesis);
+        }
+        let term = |i: usize| terms.map_or(Value::unknown(), |terms| Value::known(terms[i]));
+        let mut cells = self.assign_value_rows(region, rows, term)?;
+        cells.truncate(n);
+        Ok(cells)
+    }
+
+    /// assign `seq` as the sequence, one term per cell, with the gates enabled on every row,
+    /// so an inconsistent witness fails on the rows holding the wrong term instead of being
+    /// recomputed by the chip. needs at least 3 terms
+    pub fn assign_from_sequence(
+        &self,
+        mut layouter: impl Layouter<F>,
+        seq: &[Value<F>],
+    ) -> Result<Vec<ACell<F>>, Error> {
+        if seq.len() < 3 {
+            return Err(Error::Synthesis);
+        }
+        layouter.assign_region(
+            || "sequence",
+            |mut region| self.assign_value_rows(&mut region, seq.len() - 2, |i| seq[i]),
+        )
+    }
+
+    // the `rows + 2` cells of the filled rows, `term(i)` is the (i + 1)-th term
+    fn assign_value_rows(
+        &self,
+        region: &mut Region<'_, F>,
+        rows: usize,
+        term: impl Fn(usize) -> Value<F>,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        let mut cells = Vec::with_capacity(rows + 2);
+
+        self.enable_selector_rows(region, 0..rows)?;
+        for row in 0..rows {
+            if row + 1 < rows {
+                self.config.next_selector.enable(region, row)?;
+            }
+
+            // row i holds the terms i + 1..=i + 3
+            let [a_cell, b_cell, c_cell] = [0, 1, 2].map(|column| {
+                let value = term(row + column);
+                region.assign_advice(|| "term", self.config.advice[column], row, || value)
+            });
+            if row == 0 {
+                cells.push(ACell(a_cell?));
+                cells.push(ACell(b_cell?));
+            }
+            cells.push(ACell(c_cell?));
+        }
+        Ok(cells)
+    }
+
+    /// enable the "add" gate on every row of `rows` in the region
+    /// note: every enabled row needs its a, b and c assigned, MockProver reports the cells
+    /// of a row past the sequence as `CellNotAssigned`
+    pub fn enable_selector_rows(
+        &self,
+        region: &mut Region<'_, F>,
+        rows: Range<usize>,
+    ) -> Result<(), Error> {
+        for row in rows {
+            self.config.selector.enable(region, row)?;
+        }
+        Ok(())
+    }
+
+    /// constrain the cell to be equal to the `row`-th value of the instance column
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+
+    /// constrain `cells[indices[i]]` to row `i` of `instance`, e.g. some in between terms of
+    /// `assign_full_sequence` next to the last one. an index past `cells` is `Error::Synthesis`
+    pub fn expose_terms(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cells: &[ACell<F>],
+        indices: &[usize],
+        instance: Column<Instance>,
+    ) -> Result<(), Error> {
+        for (row, &index) in indices.iter().enumerate() {
+            let cell = cells.get(index).ok_or(Error::Synthesis)?;
+            layouter.constrain_instance(cell.cell(), instance, row)?;
+        }
+        Ok(())
+    }
+}
+
+/// `FP` lays out the regions, e.g. `FiboCircuit<Fp, V1>` to compare with the default
+#[derive(Debug)]
+pub struct FiboCircuit<F, FP = SimpleFloorPlanner> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    /// index of the last fibonacci term, which is exposed as public output, at least 2
+    /// (the seed), synthesis fails with `Error::Synthesis` below that
+    /// note: first row holds terms 1..=3 and every next row adds one term, so the
+    /// circuit uses `n - 2` rows. halo2 reserves 6 more rows (5 blinding + 1), so
+    /// `k` must satisfy `2^k >= n - 2 + 6`, e.g. k = 4 up to n = 12, k = 5 up to n = 28
+    pub n: usize,
+    /// if set, synthesize writes the value of the last term here so callers can read it back
+    pub output: Option<Rc<RefCell<Option<F>>>>,
+    /// note: defaults to `SimpleFloorPlanner`, but literals still need the type annotated,
+    /// e.g. `FiboCircuit::<Fp> { .. }`, as defaults don't take part in inference
+    pub planner: PhantomData<FP>,
+}
+
+impl<F, FP> FiboCircuit<F, FP> {
+    /// circuit without witnesses computing the `n`-th term, the seed is `Value::unknown()` in
+    /// synthesis. what `without_witnesses` returns, so keygen (e.g. on the verifier side)
+    /// only needs `n`: its keys verify proofs of any witnessed circuit of the same `n`
+    pub fn empty(n: usize) -> Self {
+        FiboCircuit {
+            a: None,
+            b: None,
+            n,
+            output: None,
+            planner: PhantomData,
+        }
+    }
+}
+
+// note: not derived, that would need `FP: PartialEq`, which the floor planners aren't
+impl<F: PartialEq, FP> PartialEq for FiboCircuit<F, FP> {
+    fn eq(&self, other: &Self) -> bool {
+        self.a == other.a && self.b == other.b && self.n == other.n && self.output == other.output
+    }
+}
+
+impl<F: Eq, FP> Eq for FiboCircuit<F, FP> {}
+
+impl<F: Field, FP: FloorPlanner> Circuit<F> for FiboCircuit<F, FP> {
+    // you could have custom config for circuit
+    type Config = FiboConfig;
+    // this is the one who decide layout of regions
+    type FloorPlanner = FP;
+
+    fn without_witnesses(&self) -> Self {
+        // TODO: halo_proof v0.3.0 doesn't have default option
+        // n shapes the circuit, so it stays even without witnesses
+        Self::empty(self.n)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        // note: columns are defined here in the circuit configure
+        // so you could decide coulmns to `reuse` in multiple chips
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let selector = meta.selector();
+        FiboChip::configure(meta, advice, selector)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // n = 1 would only expose the seed's a, with the 2nd term and the gate rows left over
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        // get actual Fibo chip instance
+        let fibo_chip = FiboChip::construct(config);
+
+        #[cfg(not(feature = "parallel"))]
+        let cells = fibo_chip.assign_sequence_single_region(
+            layouter.namespace(|| "sequence"),
+            self.a,
+            self.b,
+            self.n,
+        )?;
+        // same rows, with the witness computed up front on the rayon pool
+        #[cfg(feature = "parallel")]
+        let cells = {
+            seed_values(self.a, self.b)?;
+            let len = FiboChip::<F>::rows_for(self.n) + 2;
+            let terms = (self.a.zip(self.b)).map(|(a, b)| fibonacci_terms_parallel(a, b, len));
+            layouter.assign_region(
+                || "sequence",
+                |mut region| {
+                    fibo_chip.assign_terms_in_region(&mut region, terms.as_deref(), self.n)
+                },
+            )?
+        };
+        let out = cells.last().unwrap();
+
+        // side channel, synthesize itself can only return ()
+        if let Some(output) = &self.output {
+            out.value().map(|v| *output.borrow_mut() = Some(*v));
+        }
+
+        // the last computed cell is the public output
+        fibo_chip.expose_public(layouter.namespace(|| "out"), out, 0)?;
+        Ok(())
+    }
+}
+
+/// fibonacci circuit with a public seed: `instance[0]`, `instance[1]` are a, b and `instance[2]`
+/// is the `n`-th term, there is no private witness at all
+#[derive(Debug)]
+pub struct FiboPublicSeedCircuit<F> {
+    pub n: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F> FiboPublicSeedCircuit<F> {
+    pub fn new(n: usize) -> Self {
+        FiboPublicSeedCircuit {
+            n,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Field> Circuit<F> for FiboPublicSeedCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.n)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        FiboChip::configure_standalone(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // same as `FiboCircuit`, n = 1 would expose the seed's b as the output
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let instance = config.instance;
+        let fibo_chip = FiboChip::construct(config);
+
+        let first_row = fibo_chip
+            .assign_first_row_from_instance(layouter.namespace(|| "first row"), instance)?;
+        let (_, b_cell, c_cell) = &first_row;
+        let rows = fibo_chip.assign_rows(
+            layouter.namespace(|| "sequence"),
+            b_cell,
+            c_cell,
+            self.n.saturating_sub(3),
+        )?;
+        // no extra rows up to n = 3, the output is then in the first row
+        let out = rows
+            .last()
+            .unwrap_or(if self.n == 2 { b_cell } else { c_cell });
+
+        fibo_chip.expose_public(layouter.namespace(|| "out"), out, 2)?;
+        Ok(())
+    }
+}
+
+/// fibonacci circuit with the seed (1, 1) pinned as constants, exposes the `n`-th term at row 0
+#[derive(Debug)]
+pub struct FiboConstantSeedCircuit<F> {
+    pub n: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<F> FiboConstantSeedCircuit<F> {
+    pub fn new(n: usize) -> Self {
+        FiboConstantSeedCircuit {
+            n,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Field> Circuit<F> for FiboConstantSeedCircuit<F> {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.n)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        FiboChip::configure_standalone(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // same as `FiboCircuit`, n = 1 would expose the seed's b as the output
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let fibo_chip = FiboChip::construct(config);
+
+        let (_, b_cell, c_cell) = fibo_chip.assign_first_row_constant(
+            layouter.namespace(|| "first row"),
+            F::ONE,
+            F::ONE,
+        )?;
+        let rows = fibo_chip.assign_rows(
+            layouter.namespace(|| "sequence"),
+            &b_cell,
+            &c_cell,
+            self.n.saturating_sub(3),
+        )?;
+        let out = rows
+            .last()
+            .unwrap_or(if self.n == 2 { &b_cell } else { &c_cell });
+
+        fibo_chip.expose_public(layouter.namespace(|| "out"), out, 0)?;
+        Ok(())
+    }
+}
+
+impl<F: Field> FiboCircuit<F> {
+    pub fn builder() -> FiboCircuitBuilder<F> {
+        FiboCircuitBuilder::new()
+    }
+}
+
+impl<F: PrimeField> FiboCircuit<F> {
+    /// circuit of the `n`-th term from the integer seed (a, b)
+    /// note: unlike the builder, `n` is not checked
+    pub fn from_u64_seeds(a: u64, b: u64, n: usize) -> Self {
+        FiboCircuit {
+            a: Some(F::from(a)),
+            b: Some(F::from(b)),
+            n,
+            output: None,
+            planner: PhantomData,
+        }
+    }
+}
+
+/// builder for `FiboCircuit`, seed defaults to (1, 1) and length to 10
+#[derive(Clone, Debug)]
+pub struct FiboCircuitBuilder<F> {
+    seed: Option<(F, F)>,
+    n: Option<usize>,
+}
+
+impl<F: Field> Default for FiboCircuitBuilder<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Field> FiboCircuitBuilder<F> {
+    pub fn new() -> Self {
+        FiboCircuitBuilder {
+            seed: None,
+            n: None,
+        }
+    }
+
+    /// first two terms of the sequence
+    pub fn seed(mut self, a: F, b: F) -> Self {
+        self.seed = Some((a, b));
+        self
+    }
+
+    /// `seed` from integers
+    pub fn seed_u64(self, a: u64, b: u64) -> Self
+    where
+        F: PrimeField,
+    {
+        self.seed(F::from(a), F::from(b))
+    }
+
+    /// index of the last term, see `FiboCircuit::n`
+    pub fn length(mut self, n: usize) -> Self {
+        self.n = Some(n);
+        self
+    }
+
+    pub fn build(self) -> Result<FiboCircuit<F>, FiboError> {
+        let (a, b) = self.seed.unwrap_or((F::ONE, F::ONE));
+        let n = self.n.unwrap_or(10);
+        // the seed already holds two terms
+        if n < 2 {
+            return Err(FiboError::InvalidLength {
+                expected: 2,
+                got: n,
+            });
+        }
+
+        Ok(FiboCircuit {
+            a: Some(a),
+            b: Some(b),
+            n,
+            output: None,
+            planner: PhantomData,
+        })
+    }
+}
+
+/// run the circuit over any field with MockProver, exposing the `n`-th term as public output
+/// note: MockProver needs `Ord` on top of `Field` to sort lookup tables.
+/// the expected term is `nth_fibonacci`, field arithmetic only, so any `n` that fits `k` works,
+/// long after the integer sequence overflowed (e.g. n = 4000 at k = 12)
+#[cfg(feature = "std")]
+pub fn run_fibonacci<F: Field + Ord>(k: u32, a: F, b: F, n: usize) -> Result<MockProver<F>, Error> {
+    let circuit = FiboCircuit::<F> {
+        a: Some(a),
+        b: Some(b),
+        n,
+        output: None,
+        planner: PhantomData,
+    };
+
+    // expected output computed outside of the circuit
+    MockProver::run(k, &circuit, vec![vec![nth_fibonacci(n, a, b)]])
+}
+
+/// largest `k` params are generated for from untrusted input, e.g. the `n` of a proof bundle
+pub const MAX_K: u32 = 16;
+/// largest `n` that fits in `MAX_K`, see `check_capacity`
+pub const MAX_N: usize = (1 << MAX_K) - 4;
+
+/// smallest `k` that fits the circuit computing the `n`-th term
+/// note: the single region layout uses `FiboChip::rows_for(n)` rows, and halo2 reserves
+/// 6 more rows (5 blinding + 1), same as documented on `FiboCircuit::n`
+pub fn auto_k(n: usize) -> u32 {
+    min_k_with_blinding(n, 5)
+}
+
+/// `Ok` if the circuit computing the `n`-th term fits in `2^k` rows, blinding rows included,
+/// otherwise `InvalidLength` with the largest `n` that fits as `expected`
+/// note: halo2 reports this only at keygen or proving, as `NotEnoughRowsAvailable`
+pub fn check_capacity(k: u32, n: usize) -> Result<(), FiboError> {
+    if auto_k(n) <= k {
+        return Ok(());
+    }
+    // inverse of `auto_k`: the first row holds 3 terms, and 6 rows are reserved
+    let rows = (1usize << k).saturating_sub(6);
+    let expected = if rows == 0 { 0 } else { rows + 2 };
+    Err(FiboError::InvalidLength { expected, got: n })
+}
+
+/// same as `auto_k`, with `blinding_factors` rows reserved (plus 1) instead of 5, e.g. from
+/// `circuit_blinding_factors` of a circuit querying more rotations
+/// note: only the sequence rows are counted, a chip with a table (e.g. `RangeTableChip`)
+/// needs its table rows below the reserved ones as well
+pub fn min_k_with_blinding(n: usize, blinding_factors: usize) -> u32 {
+    // the rows don't depend on the field
+    let rows = FiboChip::<halo2_proofs::pasta::Fp>::rows_for(n) + blinding_factors + 1;
+    rows.next_power_of_two().trailing_zeros()
+}
+
+/// blinding rows halo2 reserves for the circuit, from the queries its `configure` makes
+pub fn circuit_blinding_factors<F: Field, C: Circuit<F>>() -> usize {
+    circuit_cs::<F, C>().blinding_factors()
+}
+
+/// constraint system `FiboChip::configure` builds on a fresh `ConstraintSystem`, for inspecting
+/// its degree, columns and gates without a circuit, e.g. in `gate_degree` and `describe_circuit`
+/// note: it takes no `n`, the shape doesn't depend on the witness or the length, only the rows do
+pub fn build_cs() -> ConstraintSystem<halo2_proofs::pasta::Fp> {
+    let mut meta = ConstraintSystem::default();
+    FiboChip::configure_standalone(&mut meta);
+    meta
+}
+
+/// same as `build_cs` for any circuit, from `C::configure`, e.g. `circuit_cs::<Fp, LinearRecCircuit<Fp>>()`
+pub fn circuit_cs<F: Field, C: Circuit<F>>() -> ConstraintSystem<F> {
+    let mut meta = ConstraintSystem::default();
+    C::configure(&mut meta);
+    meta
+}
+
+/// highest degree of the fibonacci gates, 2 (a selector times a linear expression), which is
+/// what a gate demands of `k` headroom. see `gate_degrees` of `build_cs` per gate
+pub fn gate_degree() -> usize {
+    gate_degrees(&build_cs())
+        .into_iter()
+        .map(|(_, degree)| degree)
+        .max()
+        .unwrap_or(0)
+}
+
+/// `(name, degree)` of each gate of the constraint system, its constraint polynomials' highest
+/// degree. unlike `ConstraintSystem::degree` the permutation's 3 isn't counted
+/// note: halo2 0.3 keeps the gates private, so the polynomials are read from its Debug output
+pub fn gate_degrees<F: Field>(meta: &ConstraintSystem<F>) -> Vec<(String, usize)> {
+    let debug = format!("{meta:?}");
+    debug
+        .split("Gate { name: \"")
+        .skip(1)
+        .map(|gate| {
+            let (name, rest) = gate.split_once('"').unwrap();
+            let mut polys = &rest[rest.find("polys: [").unwrap() + "polys: [".len()..];
+            let mut degree = 0;
+            while !polys.starts_with(']') {
+                let (poly, rest) = debug_expression_degree(polys);
+                degree = degree.max(poly);
+                polys = rest.trim_start_matches(", ");
+            }
+            (name.into(), degree)
+        })
+        .collect()
+}
+
+// degree of the `Expression` Debug output at the start of `debug`, and what follows it
+fn debug_expression_degree(debug: &str) -> (usize, &str) {
+    // end of the parentheses or braces opened at `start`
+    let after_group = |start: usize| {
+        let mut depth = 0;
+        for (i, c) in debug.char_indices().skip(start) {
+            match c {
+                '(' | '{' => depth += 1,
+                ')' | '}' => depth -= 1,
+                _ => continue,
+            }
+            if depth == 0 {
+                return &debug[i + 1..];
+            }
+        }
+        panic!("unbalanced expression {debug}")
+    };
+    let (variant, args) = debug.split_at(debug.find(['(', ' ']).unwrap());
+    match variant {
+        "Constant" => (0, after_group(variant.len())),
+        "Selector" | "Fixed" | "Advice" | "Instance" => (1, after_group(variant.len())),
+        "Negated" | "Scaled" | "Sum" | "Product" => {
+            let (lhs, rest) = debug_expression_degree(&args[1..]);
+            if variant == "Negated" {
+                return (lhs, &rest[1..]);
+            }
+            let rest = rest.strip_prefix(", ").unwrap();
+            if variant == "Scaled" {
+                // the scalar is a field element, not an expression
+                return (lhs, &rest[rest.find(')').unwrap() + 1..]);
+            }
+            let (rhs, rest) = debug_expression_degree(rest);
+            let degree = if variant == "Sum" {
+                lhs.max(rhs)
+            } else {
+                lhs + rhs
+            };
+            (degree, &rest[1..])
+        }
+        other => panic!("unknown expression {other} in {debug}"),
+    }
+}
+
+/// degree of the circuit's constraint system, from the gates its `configure` creates
+/// note: the permutation argument alone needs degree 3, so this is at least 3 for any circuit
+/// and only goes above it when a gate (or lookup) does, e.g. the add gate is 2
+pub fn cs_degree<F: Field, C: Circuit<F>>() -> usize {
+    circuit_cs::<F, C>().degree()
+}
+
+/// terms 1..=len of the sequence from (a, b), the witness of the circuit
+pub fn fibonacci_terms<F: Field>(a: F, b: F, len: usize) -> Vec<F> {
+    let (mut prev, mut cur) = (a, b);
+    (0..len)
+        .map(|_| {
+            let term = prev;
+            (prev, cur) = (cur, prev + cur);
+            term
+        })
+        .collect()
+}
+
+/// same as `fibonacci_terms`, computed in chunks on the rayon pool
+/// note: every chunk starts from its own pair of terms, found by fast doubling, and then adds
+/// along like the serial path. the serial path is a single addition per term, so this only
+/// pays off for long sequences, see `benches/witness.rs`
+#[cfg(feature = "parallel")]
+pub fn fibonacci_terms_parallel<F: Field>(a: F, b: F, len: usize) -> Vec<F> {
+    use rayon::prelude::*;
+
+    let chunk = len.div_ceil(rayon::current_num_threads()).max(1);
+    (0..len.div_ceil(chunk))
+        .into_par_iter()
+        .flat_map_iter(|i| {
+            let (start, end) = (i * chunk, ((i + 1) * chunk).min(len));
+            // terms start + 1 and start + 2
+            let (f0, f1) = fibonacci_pair::<F>(start);
+            let (mut prev, mut cur) = (a * (f1 - f0) + b * f0, a * f0 + b * f1);
+            (start..end).map(move |_| {
+                let term = prev;
+                (prev, cur) = (cur, prev + cur);
+                term
+            })
+        })
+        .collect()
+}
+
+// (F(m), F(m + 1)) of the sequence from (0, 1), by fast doubling:
+// F(2m) = F(m) * (2F(m + 1) - F(m)), F(2m + 1) = F(m)^2 + F(m + 1)^2
+#[cfg(feature = "parallel")]
+fn fibonacci_pair<F: Field>(m: usize) -> (F, F) {
+    let (mut x, mut y) = (F::ZERO, F::ONE);
+    for bit in (0..usize::BITS - m.leading_zeros()).rev() {
+        let (c, d) = (x * (y.double() - x), x.square() + y.square());
+        (x, y) = if (m >> bit) & 1 == 1 {
+            (d, c + d)
+        } else {
+            (c, d)
+        };
+    }
+    (x, y)
+}
+
+/// reference `n`-th term computed outside of the circuit, `a` is the 1st term and `b` the 2nd
+/// note: never goes through integers, the terms wrap around the modulus like in the circuit
+pub fn nth_fibonacci<F: Field>(n: usize, a: F, b: F) -> F {
+    if n <= 1 {
+        return a;
+    }
+    let (mut prev, mut cur) = (a, b);
+    for _ in 2..n {
+        (prev, cur) = (cur, prev + cur);
+    }
+    cur
+}
+
+/// `nth_fibonacci` from (1, 1) over the integers, `None` once the term overflows `u128`
+/// note: up to fib(186) this is the same as the circuit's field result, the field only
+/// wraps around much later (the pasta modulus is above 2^254)
+pub fn integer_fibonacci_checked(n: usize) -> Option<u128> {
+    let (mut prev, mut cur) = (1u128, 1u128);
+    for _ in 2..n {
+        (prev, cur) = (cur, prev.checked_add(cur)?);
+    }
+    Some(cur)
+}
+
+/// inverse of `assert_satisfied`: panics if the circuit verifies,
+/// otherwise returns the failures so callers can check which constraint broke
+#[cfg(feature = "std")]
+pub fn assert_fibonacci_unsatisfied<F: Field + Ord>(
+    k: u32,
+    circuit: &FiboCircuit<F>,
+    public_inputs: Vec<Vec<F>>,
+) -> Vec<VerifyFailure> {
+    let prover = MockProver::run(k, circuit, public_inputs).unwrap();
+    match prover.verify() {
+        Ok(()) => panic!("circuit is satisfied, but expected it to fail"),
+        Err(failures) => failures,
+    }
+}
+
+/// one line per failure of `prover.verify()`, empty if the circuit is satisfied, e.g.
+/// `gate 'add' (constraint 0) fails in region 0 ('rows') at row 2: Column('Advice', 0)@0 = 0x3, ...`
+/// note: rows are offsets inside the region, which is the circuit row for single region circuits
+#[cfg(feature = "std")]
+pub fn explain_failures<F: Field + Ord>(prover: &MockProver<F>) -> Vec<String> {
+    let Err(failures) = prover.verify() else {
+        return vec![];
+    };
+    failures.iter().map(explain_failure).collect()
+}
+
+#[cfg(feature = "std")]
+fn explain_failure(failure: &VerifyFailure) -> String {
+    match failure {
+        VerifyFailure::ConstraintNotSatisfied {
+            constraint,
+            location,
+            cell_values,
+        } => {
+            let cells: Vec<_> = cell_values
+                .iter()
+                .map(|(cell, value)| format!("{cell} = {value}"))
+                .collect();
+            format!(
+                "{} fails {}: {}",
+                explain_constraint(&constraint.to_string()),
+                explain_location(location),
+                cells.join(", ")
+            )
+        }
+        VerifyFailure::ConstraintPoisoned { constraint } => format!(
+            "{} is enabled on an unusable row, is a selector on a blinding row?",
+            explain_constraint(&constraint.to_string())
+        ),
+        VerifyFailure::CellNotAssigned {
+            gate,
+            region,
+            gate_offset,
+            column,
+            offset,
+        } => format!(
+            "{gate} needs {column:?} at offset {offset} of {region} row {gate_offset}, which is not assigned"
+        ),
+        VerifyFailure::Lookup {
+            lookup_index,
+            location,
+        } => format!(
+            "lookup {lookup_index} has no matching table row {}",
+            explain_location(location)
+        ),
+        VerifyFailure::Permutation { column, location } => format!(
+            "copy constraint on column {column} doesn't hold {}",
+            explain_location(location)
+        ),
+        // e.g. `InstanceCellNotAssigned`, `Display` is readable enough
+        failure => failure.to_string(),
+    }
+}
+
+// `Constraint 0 in gate 0 ('add')` -> `gate 'add' (constraint 0)`
+#[cfg(feature = "std")]
+fn explain_constraint(constraint: &str) -> String {
+    let gate = constraint
+        .split_once('(')
+        .and_then(|(_, rest)| rest.strip_suffix(')'));
+    let index = constraint.split_whitespace().nth(1);
+    match (gate, index) {
+        (Some(gate), Some(index)) => format!("gate {gate} (constraint {index})"),
+        _ => constraint.to_string(),
+    }
+}
+
+#[cfg(feature = "std")]
+fn explain_location(location: &FailureLocation) -> String {
+    match location {
+        FailureLocation::InRegion { region, offset } => {
+            format!("in {} at row {offset}", region.to_string().to_lowercase())
+        }
+        FailureLocation::OutsideRegion { row } => format!("outside any region at row {row}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cost_permutation_columns, cost_rows, cost_total_columns, describe_permutation,
+        fibonacci_cost, params_for, verify_fibonacci_with_params,
+    };
+    use halo2_proofs::{
+        dev::CircuitCost,
+        pasta::{Eq, Fp},
+        transcript::{Blake2bWrite, Challenge255},
+    };
+    use rand_core::OsRng;
+
+    #[test]
+    fn fibo_10th_is_55() {
+        let circuit = FiboCircuit::<Fp> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        let prover = MockProver::run(4, &circuit, vec![vec![Fp::from(55)]]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_wrong_public_output_fails() {
+        let circuit = FiboCircuit::<Fp> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        let prover = MockProver::run(4, &circuit, vec![vec![Fp::from(56)]]).unwrap();
+        // wrong output breaks the copy constraint between the last cell and instance[0]
+        let failures = prover.verify().unwrap_err();
+        assert!(failures
+            .iter()
+            .any(|f| matches!(f, VerifyFailure::Permutation { .. })));
+    }
+
+    #[test]
+    fn fibo_20th_at_k5() {
+        let circuit = FiboCircuit::<Fp> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+            n: 20,
+            output: None,
+            planner: PhantomData,
+        };
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(6765)]]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_too_small_k_errors() {
+        let circuit = FiboCircuit::<Fp> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+            n: 20,
+            output: None,
+            planner: PhantomData,
+        };
+        let result = MockProver::run(4, &circuit, vec![vec![Fp::from(6765)]]);
+        assert!(matches!(result, Err(Error::NotEnoughRowsAvailable { .. })));
+    }
+
+    #[test]
+    fn fibo_without_witnesses_does_not_panic() {
+        let circuit = FiboCircuit::<Fp> {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        }
+        .without_witnesses();
+
+        // keygen only needs the circuit shape, unknown witnesses are fine
+        keygen_vk(&params_for(4), &circuit).unwrap();
+
+        // MockProver needs actual values, so it errors instead of panicking
+        let result = MockProver::run(4, &circuit, vec![vec![Fp::from(55)]]);
+        assert!(matches!(result, Err(Error::Synthesis)));
+    }
+
+    #[test]
+    fn fibo_generic_over_field() {
+        use halo2_proofs::pasta::Fq;
+
+        run_fibonacci(4, Fp::one(), Fp::one(), 10)
+            .unwrap()
+            .assert_satisfied();
+        run_fibonacci(4, Fq::one(), Fq::one(), 10)
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_builder_defaults() {
+        let circuit = FiboCircuit::<Fp>::builder().build().unwrap();
+        assert_eq!(circuit.a, Some(Fp::one()));
+        assert_eq!(circuit.b, Some(Fp::one()));
+        assert_eq!(circuit.n, 10);
+        MockProver::run(4, &circuit, vec![vec![Fp::from(55)]])
+            .unwrap()
+            .assert_satisfied();
+
+        let circuit = FiboCircuit::builder()
+            .seed(Fp::from(2), Fp::from(3))
+            .length(5)
+            .build()
+            .unwrap();
+        // 2, 3, 5, 8, 13
+        MockProver::run(4, &circuit, vec![vec![Fp::from(13)]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_builder_rejects_short_length() {
+        assert!(matches!(
+            FiboCircuit::<Fp>::builder().length(1).build(),
+            Err(FiboError::InvalidLength {
+                expected: 2,
+                got: 1
+            })
+        ));
+        assert!(FiboCircuit::<Fp>::builder().length(2).build().is_ok());
+    }
+
+    #[test]
+    fn fibo_captures_output() {
+        let output = Rc::new(RefCell::new(None));
+        let circuit = FiboCircuit::<Fp> {
+            a: Some(Fp::one()),
+            b: Some(Fp::one()),
+            n: 10,
+            output: Some(output.clone()),
+            planner: PhantomData,
+        };
+        MockProver::run(4, &circuit, vec![vec![Fp::from(55)]])
+            .unwrap()
+            .assert_satisfied();
+        assert_eq!(*output.borrow(), Some(Fp::from(55)));
+    }
+
+    #[test]
+    fn fibo_matches_reference() {
+        for n in 2..=30 {
+            let output = Rc::new(RefCell::new(None));
+            let expected = nth_fibonacci(n, Fp::one(), Fp::one());
+            let circuit = FiboCircuit::<Fp> {
+                a: Some(Fp::one()),
+                b: Some(Fp::one()),
+                n,
+                output: Some(output.clone()),
+                planner: PhantomData,
+            };
+            MockProver::run(6, &circuit, vec![vec![expected]])
+                .unwrap()
+                .assert_satisfied();
+            assert_eq!(*output.borrow(), Some(expected), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn fibo_half_known_seed() {
+        let circuit = |a: Option<Fp>, b: Option<Fp>| FiboCircuit::<Fp> {
+            a,
+            b,
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        for (a, b) in [(Some(Fp::one()), None), (None, Some(Fp::one()))] {
+            assert!(matches!(
+                MockProver::run(4, &circuit(a, b), vec![vec![Fp::from(55)]]),
+                Err(Error::Synthesis)
+            ));
+        }
+
+        // same for `Value`s
+        let half_known = ValueSeedCircuit {
+            a: Value::known(Fp::one()),
+            b: Value::unknown(),
+        };
+        assert!(matches!(
+            MockProver::run(4, &half_known, vec![vec![Fp::from(2)]]),
+            Err(Error::Synthesis)
+        ));
+
+        // the proving path reports it before synthesis
+        assert!(matches!(
+            crate::prove_fibonacci(4, circuit(Some(Fp::one()), None), &[Fp::from(55)]),
+            Err(FiboError::MissingWitness)
+        ));
+    }
+
+    #[test]
+    fn fibo_gate_degree() {
+        use crate::{LinearRecCircuit, RangeCheckChip, RangeConfig};
+
+        // selector * (a + b - c), and selector * (a' - b) for the next row
+        assert_eq!(
+            gate_degrees(&build_cs()),
+            [("add".into(), 2), ("next row".into(), 2)]
+        );
+        assert_eq!(gate_degree(), 2);
+        // the whole circuit is raised to the permutation's 3
+        assert_eq!(cs_degree::<Fp, FiboCircuit<Fp>>(), 3);
+        // selector * (p * a + q * b - c), the fixed coefficients add one, still at the permutation
+        let linear_rec = circuit_cs::<Fp, LinearRecCircuit<Fp>>();
+        assert_eq!(gate_degrees(&linear_rec), [("linear recurrence".into(), 3)]);
+        assert_eq!(cs_degree::<Fp, LinearRecCircuit<Fp>>(), 3);
+
+        // a gate above the permutation raises it
+        struct RangeCircuit;
+        impl Circuit<Fp> for RangeCircuit {
+            type Config = RangeConfig;
+            type FloorPlanner = SimpleFloorPlanner;
+            fn without_witnesses(&self) -> Self {
+                Self
+            }
+            fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+                RangeCheckChip::<Fp, 8>::configure(meta)
+            }
+            fn synthesize(&self, _: Self::Config, _: impl Layouter<Fp>) -> Result<(), Error> {
+                Ok(())
+            }
+        }
+        assert_eq!(
+            gate_degrees(&circuit_cs::<Fp, RangeCircuit>()),
+            [("range check".into(), 9)]
+        );
+        assert_eq!(cs_degree::<Fp, RangeCircuit>(), 9);
+    }
+
+    #[test]
+    fn fibo_matches_integer_until_overflow() {
+        use halo2_proofs::pasta::group::ff::PrimeField;
+
+        assert_eq!(integer_fibonacci_checked(10), Some(55));
+        assert!(integer_fibonacci_checked(186).is_some());
+        assert_eq!(integer_fibonacci_checked(187), None);
+
+        for n in [2, 3, 64, 128, 186] {
+            let output = Rc::new(RefCell::new(None));
+            let circuit = FiboCircuit::<Fp> {
+                a: Some(Fp::one()),
+                b: Some(Fp::one()),
+                n,
+                output: Some(output.clone()),
+                planner: PhantomData,
+            };
+            let expected = Fp::from_u128(integer_fibonacci_checked(n).unwrap());
+            MockProver::run(auto_k(n), &circuit, vec![vec![expected]])
+                .unwrap()
+                .assert_satisfied();
+            assert_eq!(*output.borrow(), Some(expected), "n = {n}");
+        }
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+
+        // note: compared in the field, large seeds wrap around the modulus
+        #[test]
+        fn fibo_random_seed_matches_reference(a: u64, b: u64, n in 2usize..50) {
+            let (a, b) = (Fp::from(a), Fp::from(b));
+            let expected = nth_fibonacci(n, a, b);
+            let output = Rc::new(RefCell::new(None));
+            let circuit = FiboCircuit::<Fp> {
+                a: Some(a),
+                b: Some(b),
+                n,
+                output: Some(output.clone()),
+                planner: PhantomData,
+            };
+            let prover = MockProver::run(auto_k(n), &circuit, vec![vec![expected]]).unwrap();
+            proptest::prop_assert_eq!(prover.verify(), Ok(()));
+            proptest::prop_assert_eq!(*output.borrow(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn fibo_auto_k() {
+        assert_eq!(auto_k(10), 4);
+        for n in 2..=100 {
+            let k = auto_k(n);
+            run_fibonacci(k, Fp::one(), Fp::one(), n)
+                .unwrap()
+                .assert_satisfied();
+            // and it's the smallest one
+            assert!(matches!(
+                run_fibonacci(k - 1, Fp::one(), Fp::one(), n),
+                Err(Error::NotEnoughRowsAvailable { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn fibo_check_capacity() {
+        assert!(matches!(
+            check_capacity(3, 100),
+            Err(FiboError::InvalidLength {
+                expected: 4,
+                got: 100
+            })
+        ));
+        assert!(check_capacity(10, 100).is_ok());
+        // 12 terms take 10 of the 16 rows, the reserved 6 the rest
+        assert!(check_capacity(4, 12).is_ok());
+        assert!(matches!(
+            check_capacity(4, 13),
+            Err(FiboError::InvalidLength { expected: 12, .. })
+        ));
+    }
+
+    #[test]
+    fn fibo_min_k_with_blinding() {
+        let blinding = circuit_blinding_factors::<Fp, FiboCircuit<Fp>>();
+        assert_eq!(blinding, 5);
+        for n in 2..=100 {
+            assert_eq!(min_k_with_blinding(n, blinding), auto_k(n));
+        }
+        // 8 rows + 5 + 1 fit in 16, 8 + 10 + 1 don't
+        assert_eq!(min_k_with_blinding(10, 10), 5);
+    }
+
+    #[test]
+    fn fibo_unsatisfied_helper() {
+        let circuit = FiboCircuit::builder().build().unwrap();
+
+        // wrong public output
+        let failures = assert_fibonacci_unsatisfied(4, &circuit, vec![vec![Fp::from(54)]]);
+        assert!(failures.iter().all(|f| matches!(
+            f,
+            VerifyFailure::Permutation { .. } | VerifyFailure::ConstraintNotSatisfied { .. }
+        )));
+
+        // wrong seed makes the honest output unreachable
+        let circuit = FiboCircuit::builder()
+            .seed(Fp::from(1), Fp::from(2))
+            .build()
+            .unwrap();
+        assert!(!assert_fibonacci_unsatisfied(4, &circuit, vec![vec![Fp::from(55)]]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "circuit is satisfied")]
+    fn fibo_unsatisfied_helper_panics_on_valid_circuit() {
+        let circuit = FiboCircuit::builder().build().unwrap();
+        assert_fibonacci_unsatisfied(4, &circuit, vec![vec![Fp::from(55)]]);
+    }
+
+    #[test]
+    fn fibo_public_seed() {
+        // 2, 3, 5, 8, 13, 21, 34, 55, 89, 144
+        let public_inputs = vec![vec![Fp::from(2), Fp::from(3), Fp::from(144)]];
+        MockProver::run(4, &FiboPublicSeedCircuit::new(10), public_inputs)
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_public_seed_inconsistent_fails() {
+        // output of the (2, 3) sequence, but the public seed is (2, 4)
+        let public_inputs = vec![vec![Fp::from(2), Fp::from(4), Fp::from(144)]];
+        let prover = MockProver::run(4, &FiboPublicSeedCircuit::new(10), public_inputs).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn fibo_public_seed_too_short_is_rejected() {
+        for n in [0, 1] {
+            let public_inputs = vec![vec![Fp::from(2), Fp::from(3), Fp::from(3)]];
+            assert!(matches!(
+                MockProver::run(4, &FiboPublicSeedCircuit::new(n), public_inputs),
+                Err(Error::Synthesis)
+            ));
+        }
+    }
+
+    #[test]
+    fn fibo_constant_seed_proves() {
+        let params = params_for(4);
+        let circuit = FiboConstantSeedCircuit::<Fp>::new(10);
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        let pk = keygen_pk(&params, vk.clone(), &circuit).unwrap();
+
+        let public_inputs = [Fp::from(55)];
+        let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+        create_proof(
+            &params,
+            &pk,
+            &[circuit],
+            &[&[&public_inputs]],
+            OsRng,
+            &mut transcript,
+        )
+        .unwrap();
+        let proof = transcript.finalize();
+        verify_fibonacci_with_params(&params, &vk, &[&public_inputs], &proof).unwrap();
+    }
+
+    #[test]
+    fn fibo_constant_seed_too_short_is_rejected() {
+        for n in [0, 1] {
+            assert!(matches!(
+                MockProver::run(4, &FiboConstantSeedCircuit::new(n), vec![vec![Fp::one()]]),
+                Err(Error::Synthesis)
+            ));
+        }
+    }
+
+    // `FiboConstantSeedCircuit`, but the prover puts (2, 2) into the pinned cells
+    struct TamperedSeedCircuit;
+
+    impl Circuit<Fp> for TamperedSeedCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            TamperedSeedCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let (b_cell, c_cell) = layouter.assign_region(
+                || "first row",
+                |mut region| {
+                    config.selector.enable(&mut region, 0)?;
+                    let two = Value::known(Fp::from(2));
+                    let a = region.assign_advice(|| "a", config.advice[0], 0, || two)?;
+                    let b = region.assign_advice(|| "b", config.advice[1], 0, || two)?;
+                    // same constraint as `assign_advice_from_constant`, with the wrong value
+                    region.constrain_constant(a.cell(), Fp::one())?;
+                    region.constrain_constant(b.cell(), Fp::one())?;
+                    let c = region.assign_advice(|| "c", config.advice[2], 0, || two + two)?;
+                    Ok((ACell(b), ACell(c)))
+                },
+            )?;
+
+            let fibo_chip = FiboChip::construct(config);
+            let rows =
+                fibo_chip.assign_rows(layouter.namespace(|| "sequence"), &b_cell, &c_cell, 7)?;
+            fibo_chip.expose_public(layouter.namespace(|| "out"), rows.last().unwrap(), 0)
+        }
+    }
+
+    #[test]
+    fn fibo_constant_seed_tampered_fails() {
+        // output of the (2, 2) sequence, so only the constant constraint can fail
+        let expected = nth_fibonacci(10, Fp::from(2), Fp::from(2));
+        let prover = MockProver::run(4, &TamperedSeedCircuit, vec![vec![expected]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures
+            .iter()
+            .all(|f| matches!(f, VerifyFailure::Permutation { .. })));
+    }
+
+    // first row with seed (1, 1), then `assign_rows` for the rest
+    struct AssignRowsCircuit(usize);
+
+    impl Circuit<Fp> for AssignRowsCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            AssignRowsCircuit(self.0)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let (_, b, c) = layouter.assign_region(
+                || "first row",
+                |mut region| {
+                    chip.assign_first_row(
+                        &mut region,
+                        0,
+                        Value::known(Fp::one()),
+                        Value::known(Fp::one()),
+                    )
+                },
+            )?;
+            let cells = chip.assign_rows(layouter.namespace(|| "rows"), &b, &c, self.0)?;
+
+            assert_eq!(cells.len(), self.0);
+            let expected = nth_fibonacci(self.0 + 3, Fp::one(), Fp::one());
+            cells
+                .last()
+                .unwrap()
+                .value()
+                .assert_if_known(|v| **v == expected);
+            Ok(())
+        }
+    }
+
+    // hands the cells out of synthesize, then ties the last term to the first row by hand
+    #[derive(Default)]
+    struct ReadCellCircuit {
+        values: RefCell<Vec<Fp>>,
+    }
+
+    impl Circuit<Fp> for ReadCellCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let cells = chip.assign_sequence_single_region(
+                layouter.namespace(|| "sequence"),
+                Some(Fp::one()),
+                Some(Fp::one()),
+                4,
+            )?;
+            for cell in &cells {
+                cell.value().map(|v| self.values.borrow_mut().push(*v));
+            }
+            // the 1st and 2nd terms are both one
+            layouter.assign_region(
+                || "manual copy",
+                |mut region| region.constrain_equal(cells[0].cell(), cells[1].cell()),
+            )
+        }
+    }
+
+    #[test]
+    fn fibo_acell_value_after_synthesis() {
+        let circuit = ReadCellCircuit::default();
+        MockProver::run(4, &circuit, vec![vec![]])
+            .unwrap()
+            .assert_satisfied();
+        let values = circuit.values.borrow();
+        assert_eq!(*values, [1, 1, 2, 3].map(Fp::from).to_vec());
+    }
+
+    // two sequences of `n` terms in one region, the second starts right below the first
+    struct TwoSequencesCircuit {
+        seeds: [(Fp, Fp); 2],
+        n: usize,
+    }
+
+    impl Circuit<Fp> for TwoSequencesCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                seeds: self.seeds,
+                n: self.n,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let rows = self.n - 2;
+            let outputs = layouter.assign_region(
+                || "two sequences",
+                |mut region| {
+                    let mut outputs = vec![];
+                    for (i, (a, b)) in self.seeds.into_iter().enumerate() {
+                        let offset = i * rows;
+                        let (_, mut prev_b, mut prev_c) = chip.assign_first_row(
+                            &mut region,
+                            offset,
+                            Value::known(a),
+                            Value::known(b),
+                        )?;
+                        for row in 1..rows {
+                            let c = chip.assign_row(&mut region, offset + row, &prev_b, &prev_c)?;
+                            (prev_b, prev_c) = (prev_c, c);
+                        }
+                        outputs.push(prev_c);
+                    }
+                    Ok(outputs)
+                },
+            )?;
+            for (row, out) in outputs.iter().enumerate() {
+                chip.expose_public(layouter.namespace(|| "out"), out, row)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fibo_two_sequences_one_region() {
+        let seeds = [(Fp::one(), Fp::one()), (Fp::from(2), Fp::one())];
+        let circuit = TwoSequencesCircuit { seeds, n: 10 };
+        // 55 and 76
+        let outputs = seeds.map(|(a, b)| nth_fibonacci(10, a, b)).to_vec();
+        // 2 * 8 rows + 6 reserved
+        MockProver::run(5, &circuit, vec![outputs.clone()])
+            .unwrap()
+            .assert_satisfied();
+
+        // swapped outputs, each sequence is checked against its own row
+        let swapped = outputs.into_iter().rev().collect();
+        assert!(MockProver::run(5, &circuit, vec![swapped])
+            .unwrap()
+            .verify()
+            .is_err());
+    }
+
+    #[test]
+    fn fibo_assign_rows_returns_all_cells() {
+        // 3 terms in the first row, 7 more up to the 10th
+        MockProver::run(4, &AssignRowsCircuit(7), vec![vec![]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    // the old layout, one region per row chained by copy constraints
+    #[derive(Debug)]
+    struct PerRowCircuit(usize);
+
+    impl Circuit<Fp> for PerRowCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            PerRowCircuit(self.0)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let cells = chip.assign_full_sequence(
+                layouter.namespace(|| "sequence"),
+                Some(Fp::one()),
+                Some(Fp::one()),
+                self.0,
+            )?;
+            chip.expose_public(layouter.namespace(|| "out"), cells.last().unwrap(), 0)
+        }
+    }
+
+    #[test]
+    fn fibo_per_row_region_names() {
+        // the 10th term is the c cell of the 7th row after the first one
+        let prover = MockProver::run(4, &PerRowCircuit(10), vec![vec![Fp::from(56)]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        let regions: Vec<_> = failures
+            .iter()
+            .filter_map(|f| match f {
+                VerifyFailure::Permutation {
+                    location: FailureLocation::InRegion { region, .. },
+                    ..
+                } => Some(region.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(regions, ["Region 7 ('row 7')"]);
+    }
+
+    #[test]
+    fn fibo_single_region_saves_copies() {
+        let k = 4;
+        let out = vec![vec![Fp::from(55)]];
+        MockProver::run(k, &PerRowCircuit(10), out.clone())
+            .unwrap()
+            .assert_satisfied();
+        MockProver::run(k, &FiboCircuit::builder().build().unwrap(), out)
+            .unwrap()
+            .assert_satisfied();
+
+        // the saving is in the permutation: per row regions copy b, c of the previous row for
+        // the 7 rows after the first, the single region chains rows with the "next row" gate
+        // and only copies the output into the instance
+        let per_row = describe_permutation(&PerRowCircuit(10))
+            .unwrap()
+            .copies
+            .len();
+        let single = describe_permutation(&FiboCircuit::<Fp>::builder().build().unwrap())
+            .unwrap()
+            .copies
+            .len();
+        assert_eq!((single, per_row), (1, 2 * 7 + 1));
+
+        // note: SimpleFloorPlanner already packs the one-row regions back to back, so the rows
+        // don't drop, both take 8. the same 5 columns (3 advice, instance, constants) stay in
+        // the permutation, and the "next row" selector is one more fixed column whose
+        // evaluation makes the proof a field element larger
+        let per_row = CircuitCost::<Eq, _>::measure(k, &PerRowCircuit(10));
+        let single = fibonacci_cost(k, 10);
+        assert_eq!((cost_rows(&single), cost_rows(&per_row)), (8, 8));
+        assert_eq!(
+            (
+                cost_permutation_columns(&single),
+                cost_permutation_columns(&per_row)
+            ),
+            (5, 5)
+        );
+        assert_eq!(
+            (cost_total_columns(&single), cost_total_columns(&per_row)),
+            (7, 6)
+        );
+        assert_eq!(
+            usize::from(single.proof_size(1)),
+            usize::from(per_row.proof_size(1)) + 32
+        );
+    }
+
+    // two fibonacci chips laid out on the same advice columns
+    struct SharedColumnsCircuit;
+
+    impl Circuit<Fp> for SharedColumnsCircuit {
+        type Config = (FiboConfig, FiboConfig);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            SharedColumnsCircuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let advice = [(); 3].map(|_| meta.advice_column());
+            // each chip still gets its own selector, so the gates don't overlap
+            let [s_first, s_second] = [meta.selector(), meta.selector()];
+            let first = FiboChip::configure(meta, advice, s_first);
+            let second = FiboChip::configure(meta, advice, s_second);
+            (first, second)
+        }
+
+        fn synthesize(
+            &self,
+            (first, second): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            for (i, (config, a, b, n)) in [(first, 1, 1, 10), (second, 2, 3, 5)]
+                .into_iter()
+                .enumerate()
+            {
+                let chip = FiboChip::construct(config);
+                let cells = chip.assign_full_sequence(
+                    layouter.namespace(|| format!("sequence {i}")),
+                    Some(Fp::from(a)),
+                    Some(Fp::from(b)),
+                    n,
+                )?;
+                chip.expose_public(layouter.namespace(|| "out"), cells.last().unwrap(), 0)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fibo_chips_share_advice_columns() {
+        let public_inputs = vec![vec![Fp::from(55)], vec![Fp::from(13)]];
+        MockProver::run(5, &SharedColumnsCircuit, public_inputs)
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    // an "add" gate off by one, c = a + b + 1, that the last row doesn't follow
+    struct OffByOneCircuit;
+
+    impl Circuit<Fp> for OffByOneCircuit {
+        type Config = ([Column<Advice>; 3], Selector);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let advice = [(); 3].map(|_| meta.advice_column());
+            let selector = meta.selector();
+            meta.create_gate("add", |meta| {
+                // | col_a | col_b |   col_c   | selector |
+                // |   a   |   b   | a + b + 1 |     s    |
+                let s = meta.query_selector(selector);
+                let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+                vec![s * (a + b + Expression::Constant(Fp::one()) - c)]
+            });
+            (advice, selector)
+        }
+
+        fn synthesize(
+            &self,
+            (advice, selector): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            layouter.assign_region(
+                || "rows",
+                |mut region| {
+                    for (row, (a, b, c)) in
+                        [(1, 1, 3), (1, 3, 5), (3, 5, 8)].into_iter().enumerate()
+                    {
+                        selector.enable(&mut region, row)?;
+                        for (column, value) in advice.into_iter().zip([a, b, c]) {
+                            region.assign_advice(
+                                || "cell",
+                                column,
+                                row,
+                                || Value::known(Fp::from(value)),
+                            )?;
+                        }
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn fibo_explain_failures() {
+        let prover = MockProver::run(4, &OffByOneCircuit, vec![]).unwrap();
+        let lines = explain_failures(&prover);
+        assert_eq!(lines.len(), 1, "{lines:?}");
+        assert!(lines[0].contains("gate 'add'"), "{}", lines[0]);
+        assert!(lines[0].contains("at row 2"), "{}", lines[0]);
+        // the offending cells, 3 + 5 = 8
+        assert!(lines[0].contains("= 0x8"), "{}", lines[0]);
+
+        let prover = run_fibonacci(4, Fp::one(), Fp::one(), 10).unwrap();
+        assert!(explain_failures(&prover).is_empty());
+    }
+
+    // first row from (1, 1), then one row of `assign_row_with` computing the 4th term
+    struct StepWithCircuit(fn(&Fp, &Fp) -> Fp);
+
+    impl Circuit<Fp> for StepWithCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let (_, b, c) = layouter.assign_region(
+                || "first row",
+                |mut region| {
+                    chip.assign_first_row(
+                        &mut region,
+                        0,
+                        Value::known(Fp::one()),
+                        Value::known(Fp::one()),
+                    )
+                },
+            )?;
+            let d = layouter.assign_region(
+                || "next row",
+                |mut region| chip.assign_row_with(&mut region, 0, &b, &c, self.0),
+            )?;
+            chip.expose_public(layouter.namespace(|| "out"), &d, 0)
+        }
+    }
+
+    #[test]
+    fn fibo_assign_row_with() {
+        MockProver::run(4, &StepWithCircuit(|b, c| *b + *c), vec![vec![Fp::from(3)]])
+            .unwrap()
+            .assert_satisfied();
+
+        // 1 + 2 * 2, even with the public output agreeing with `f`, the gate rejects the row
+        let circuit = StepWithCircuit(|b, c| *b + *c + *c);
+        let failures = MockProver::run(4, &circuit, vec![vec![Fp::from(5)]])
+            .unwrap()
+            .verify()
+            .unwrap_err();
+        assert!(failures
+            .iter()
+            .all(|f| matches!(f, VerifyFailure::ConstraintNotSatisfied { .. })));
+    }
+
+    // `assign_first_row` with the seed as `Value`s, exposing the 3rd term
+    #[derive(Default)]
+    struct ValueSeedCircuit {
+        a: Value<Fp>,
+        b: Value<Fp>,
+    }
+
+    impl Circuit<Fp> for ValueSeedCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let (_, _, c) = layouter.assign_region(
+                || "first row",
+                |mut region| chip.assign_first_row(&mut region, 0, self.a, self.b),
+            )?;
+            chip.expose_public(layouter.namespace(|| "out"), &c, 0)
+        }
+    }
+
+    #[test]
+    fn fibo_first_row_values() {
+        let k = 4;
+        let params = params_for(k);
+        let circuit = ValueSeedCircuit {
+            a: Value::known(Fp::from(2)),
+            b: Value::known(Fp::from(3)),
+        };
+        let instance = [Fp::from(5)];
+
+        // keygen with unknown values
+        let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+        let pk = keygen_pk(&params, vk.clone(), &circuit.without_witnesses()).unwrap();
+
+        let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+        create_proof(
+            &params,
+            &pk,
+            &[circuit],
+            &[&[&instance]],
+            OsRng,
+            &mut transcript,
+        )
+        .unwrap();
+        let proof = transcript.finalize();
+        assert!(verify_fibonacci_with_params(&params, &vk, &[&instance], &proof).is_ok());
+    }
+
+    #[test]
+    fn fibo_v1_planner() {
+        let circuit = FiboCircuit::<Fp, floor_planner::V1> {
+            a: Some(Fp::one()),
+            b: Some(Fp::one()),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        MockProver::run(4, &circuit, vec![vec![Fp::from(55)]])
+            .unwrap()
+            .assert_satisfied();
+
+        // a single region, so `V1` lays it out the same: a V1 proof verifies with the default vk
+        let params = params_for(4);
+        let default = FiboCircuit::<Fp>::empty(10);
+        let vk = keygen_vk(&params, &default).unwrap();
+        let pk = keygen_pk(&params, keygen_vk(&params, &circuit).unwrap(), &circuit).unwrap();
+        let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+        create_proof(
+            &params,
+            &pk,
+            &[circuit],
+            &[&[&[Fp::from(55)]]],
+            OsRng,
+            &mut transcript,
+        )
+        .unwrap();
+        let proof = transcript.finalize();
+        assert!(verify_fibonacci_with_params(&params, &vk, &[&[Fp::from(55)]], &proof).is_ok());
+    }
+
+    // the single region sequence of `FiboCircuit`, with the gate enabled on one more row
+    struct OffByOneSelectorCircuit;
+
+    impl Circuit<Fp> for OffByOneSelectorCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            layouter.assign_region(
+                || "sequence",
+                |mut region| {
+                    // 8 rows up to the 10th term
+                    chip.assign_sequence_in_region(
+                        &mut region,
+                        Some(Fp::one()),
+                        Some(Fp::one()),
+                        10,
+                    )?;
+                    chip.enable_selector_rows(&mut region, 0..9)
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn fibo_selector_rows_off_by_one() {
+        let failures = MockProver::run(4, &OffByOneSelectorCircuit, vec![vec![]])
+            .unwrap()
+            .verify()
+            .unwrap_err();
+        assert!(!failures.is_empty());
+        for failure in &failures {
+            assert!(
+                matches!(
+                    failure,
+                    VerifyFailure::CellNotAssigned { gate, offset: 8, .. }
+                        if gate.to_string().contains("'add'")
+                ),
+                "{failure}"
+            );
+        }
+    }
+
+    struct ClonedChipCircuit;
+
+    impl Circuit<Fp> for ClonedChipCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::new(config);
+            let other = chip.clone();
+            let first = chip.assign_sequence_single_region(
+                layouter.namespace(|| "first"),
+                Some(Fp::one()),
+                Some(Fp::one()),
+                10,
+            )?;
+            let second = other.assign_sequence_single_region(
+                layouter.namespace(|| "second"),
+                Some(Fp::from(2)),
+                Some(Fp::one()),
+                10,
+            )?;
+            chip.expose_public(layouter.namespace(|| "first out"), first.last().unwrap(), 0)?;
+            other.expose_public(
+                layouter.namespace(|| "second out"),
+                second.last().unwrap(),
+                1,
+            )
+        }
+    }
+
+    #[test]
+    fn fibo_cloned_chip() {
+        let outputs = vec![Fp::from(55), Fp::from(76)];
+        MockProver::run(5, &ClonedChipCircuit, vec![outputs])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_rows_for() {
+        assert_eq!(FiboChip::<Fp>::rows_for(10), 8);
+        assert_eq!(FiboChip::<Fp>::rows_for(2), 1);
+        // n = 12 takes 10 rows, with the 6 reserved that fills k = 4
+        assert_eq!(auto_k(12), 4);
+        assert_eq!(auto_k(13), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "3 distinct advice columns")]
+    fn fibo_configure_duplicate_columns() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let (col, other) = (meta.advice_column(), meta.advice_column());
+        let selector = meta.selector();
+        FiboChip::configure(&mut meta, [col, other, col], selector);
+    }
+
+    #[test]
+    fn fibo_long_sequence_matches_field_reference() {
+        for (k, n) in [(10, 1000), (12, 4000)] {
+            let output = Rc::new(RefCell::new(None));
+            let circuit = FiboCircuit::<Fp> {
+                output: Some(output.clone()),
+                ..FiboCircuit::builder().length(n).build().unwrap()
+            };
+            let expected = nth_fibonacci(n, Fp::one(), Fp::one());
+            MockProver::run(k, &circuit, vec![vec![expected]])
+                .unwrap()
+                .assert_satisfied();
+            // the last cell of the circuit, far past the integers
+            assert_eq!(*output.borrow(), Some(expected));
+            assert_eq!(integer_fibonacci_checked(n), None);
+        }
+    }
+
+    // what a gadget taking raw cells would do
+    fn copy_raw(
+        region: &mut Region<'_, Fp>,
+        cell: &AssignedCell<Fp, Fp>,
+        column: Column<Advice>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        cell.copy_advice(|| "raw", region, column, 0)
+    }
+
+    struct RawCellCircuit;
+
+    impl Circuit<Fp> for RawCellCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let column = config.advice[0];
+            let chip = FiboChip::construct(config);
+            let cells = chip.assign_sequence_single_region(
+                layouter.namespace(|| "sequence"),
+                Some(Fp::one()),
+                Some(Fp::one()),
+                10,
+            )?;
+            let copied = layouter.assign_region(
+                || "raw",
+                |mut region| copy_raw(&mut region, cells.last().unwrap().as_ref(), column),
+            )?;
+            chip.expose_public(layouter.namespace(|| "out"), &ACell::from(copied), 0)
+        }
+    }
+
+    #[test]
+    fn fibo_acell_as_raw_cell() {
+        MockProver::run(5, &RawCellCircuit, vec![vec![Fp::from(55)]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_u64_seeds() {
+        let manual = FiboCircuit::<Fp> {
+            a: Some(Fp::from(2)),
+            b: Some(Fp::from(3)),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        assert_eq!(FiboCircuit::from_u64_seeds(2, 3, 10), manual);
+        let built = FiboCircuit::builder().seed_u64(2, 3).build().unwrap();
+        assert_eq!(built, manual);
+        assert_ne!(FiboCircuit::from_u64_seeds(3, 2, 10), manual);
+    }
+
+    #[test]
+    fn fibo_too_short_is_rejected() {
+        for n in [0, 1] {
+            let circuit = FiboCircuit::<Fp>::from_u64_seeds(1, 1, n);
+            assert!(matches!(
+                MockProver::run(4, &circuit, vec![vec![Fp::one()]]),
+                Err(Error::Synthesis)
+            ));
+            let params = crate::params_for(4);
+            assert!(matches!(
+                keygen_vk(&params, &FiboCircuit::<Fp>::empty(n)),
+                Err(Error::Synthesis)
+            ));
+        }
+        // the seed alone is fine
+        run_fibonacci(4, Fp::one(), Fp::from(2), 2)
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    // exposes the terms at `indices` of the first 10
+    struct ExposeTermsCircuit(&'static [usize]);
+
+    impl Circuit<Fp> for ExposeTermsCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let instance = config.instance;
+            let chip = FiboChip::construct(config);
+            let cells = chip.assign_sequence_single_region(
+                layouter.namespace(|| "sequence"),
+                Some(Fp::one()),
+                Some(Fp::one()),
+                10,
+            )?;
+            chip.expose_terms(layouter.namespace(|| "terms"), &cells, self.0, instance)
+        }
+    }
+
+    #[test]
+    fn fibo_expose_terms() {
+        // 5th and 10th terms
+        let circuit = ExposeTermsCircuit(&[4, 9]);
+        MockProver::run(4, &circuit, vec![vec![Fp::from(5), Fp::from(55)]])
+            .unwrap()
+            .assert_satisfied();
+        assert!(
+            MockProver::run(4, &circuit, vec![vec![Fp::from(8), Fp::from(55)]])
+                .unwrap()
+                .verify()
+                .is_err()
+        );
+        assert!(matches!(
+            MockProver::run(4, &ExposeTermsCircuit(&[10]), vec![vec![Fp::zero()]]),
+            Err(Error::Synthesis)
+        ));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn fibo_parallel_terms() {
+        let (a, b) = (Fp::from(2), Fp::from(7));
+        for len in [0, 1, 2, 3, 10, 1000, 4097] {
+            let serial = fibonacci_terms(a, b, len);
+            assert_eq!(fibonacci_terms_parallel(a, b, len), serial, "len = {len}");
+            if len > 0 {
+                assert_eq!(serial[len - 1], nth_fibonacci(len, a, b));
+            }
+        }
+    }
+
+    struct PrecomputedCircuit(Option<Vec<Fp>>);
+
+    impl Circuit<Fp> for PrecomputedCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(None)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let cells = layouter.assign_region(
+                || "sequence",
+                |mut region| chip.assign_terms_in_region(&mut region, self.0.as_deref(), 10),
+            )?;
+            chip.expose_public(layouter.namespace(|| "out"), &cells[9], 0)
+        }
+    }
+
+    #[test]
+    fn fibo_assign_precomputed_terms() {
+        let terms = fibonacci_terms(Fp::one(), Fp::one(), 10);
+        let circuit = PrecomputedCircuit(Some(terms.clone()));
+        MockProver::run(4, &circuit, vec![vec![Fp::from(55)]])
+            .unwrap()
+            .assert_satisfied();
+
+        // the gates still check the witness
+        let mut wrong = terms.clone();
+        wrong[5] += Fp::one();
+        let prover = MockProver::run(
+            4,
+            &PrecomputedCircuit(Some(wrong)),
+            vec![vec![Fp::from(55)]],
+        );
+        assert!(prover.unwrap().verify().is_err());
+        // too few terms for the rows
+        let short = PrecomputedCircuit(Some(terms[..9].to_vec()));
+        assert!(matches!(
+            MockProver::run(4, &short, vec![vec![Fp::from(55)]]),
+            Err(Error::Synthesis)
+        ));
+    }
+
+    struct SequenceCircuit(Vec<Value<Fp>>);
+
+    impl Circuit<Fp> for SequenceCircuit {
+        type Config = FiboConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(vec![Value::unknown(); self.0.len()])
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let cells = chip.assign_from_sequence(layouter.namespace(|| "sequence"), &self.0)?;
+            chip.expose_public(layouter.namespace(|| "out"), cells.last().unwrap(), 0)
+        }
+    }
+
+    #[test]
+    fn fibo_assign_from_sequence() {
+        let seq = |wrong: Option<usize>| {
+            let mut seq = fibonacci_terms(Fp::one(), Fp::one(), 10);
+            if let Some(i) = wrong {
+                seq[i] += Fp::one();
+            }
+            SequenceCircuit(seq.into_iter().map(Value::known).collect())
+        };
+        // the output is exposed as public, what it is doesn't matter for the gates
+        let failing_rows = |circuit: &SequenceCircuit, output: u64| -> Vec<usize> {
+            let prover = MockProver::run(4, circuit, vec![vec![Fp::from(output)]]).unwrap();
+            prover.verify().map_or_else(
+                |failures| {
+                    failures
+                        .iter()
+                        .map(|f| match f {
+                            VerifyFailure::ConstraintNotSatisfied {
+                                location: FailureLocation::InRegion { offset, .. },
+                                ..
+                            } => *offset,
+                            _ => panic!("unexpected failure {f:?}"),
+                        })
+                        .collect()
+                },
+                |()| vec![],
+            )
+        };
+
+        assert!(failing_rows(&seq(None), 55).is_empty());
+        // the 10th term is only the c of the last row
+        assert_eq!(failing_rows(&seq(Some(9)), 56), [7]);
+        // a middle term is copied along, every "add" row holding it fails
+        assert_eq!(failing_rows(&seq(Some(5)), 55), [3, 4, 5]);
+
+        assert!(matches!(
+            MockProver::run(
+                4,
+                &SequenceCircuit(vec![Value::known(Fp::one()); 2]),
+                vec![vec![]]
+            ),
+            Err(Error::Synthesis)
+        ));
+    }
+
+    #[test]
+    fn fibo_config_display() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        // shift the indices, so each one is checked
+        meta.advice_column();
+        let config = FiboChip::configure_standalone(&mut meta);
+        assert_eq!(
+            config.to_string(),
+            "FiboConfig { advice: [1,2,3], selector: 0, next_selector: 1, constants: 0, instance: 0 }"
+        );
+    }
+
+    #[test]
+    fn fibo_build_cs() {
+        let meta = build_cs();
+        // the fields are private, Debug lists the gates
+        // note: 2 gates and 2 selectors, not 1 and 1: the "next row" gate chaining the rows of
+        // the single region has its own selector
+        let debug = format!("{meta:?}");
+        let gates: Vec<_> = debug
+            .split("Gate { name: ")
+            .skip(1)
+            .map(|gate| gate.split('"').nth(1).unwrap())
+            .collect();
+        assert_eq!(gates, ["add", "next row"]);
+        assert!(debug.contains("num_selectors: 2,"), "{debug}");
+        assert!(debug.contains("num_advice_columns: 3,"), "{debug}");
+        // the same constraint system as the circuit's
+        assert_eq!(debug, format!("{:?}", circuit_cs::<Fp, FiboCircuit<Fp>>()));
+        assert_eq!(meta.degree(), cs_degree::<Fp, FiboCircuit<Fp>>());
+    }
+}
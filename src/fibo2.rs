@@ -0,0 +1,174 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// two column variant of `FiboConfig`, each row holds two consecutive terms
+#[derive(Clone, Debug)]
+pub struct FiboConfig2 {
+    pub advice: [Column<Advice>; 2],
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+pub struct FiboChip2<F: Field> {
+    config: FiboConfig2,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> FiboChip2<F> {
+    pub fn construct(config: FiboConfig2) -> Self {
+        FiboChip2 {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> FiboConfig2 {
+        let col_a = meta.advice_column();
+        let col_b = meta.advice_column();
+        let selector = meta.selector();
+        let instance = meta.instance_column();
+
+        meta.enable_equality(col_b);
+        meta.enable_equality(instance);
+
+        meta.create_gate("add", |meta| {
+            // | col_a | col_b | selector |
+            // |   a   |   b   |     s    |
+            // |   b   | a + b |          |
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let a_next = meta.query_advice(col_a, Rotation::next());
+            let b_next = meta.query_advice(col_b, Rotation::next());
+
+            // the next row shifts by one term, so no copy constraint is needed
+            vec![s.clone() * (a_next - b.clone()), s * (b_next - a - b)]
+        });
+
+        FiboConfig2 {
+            advice: [col_a, col_b],
+            selector,
+            instance,
+        }
+    }
+
+    /// assign the sequence up to the `n`-th term in one region, returning the cell of the last term
+    /// note: uses `n - 1` rows, one more than `FiboChip` but with one column less
+    pub fn assign_full_sequence(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+        n: usize,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "sequence",
+            |mut region| {
+                let rows = n.saturating_sub(1).max(1);
+                let mut a_val = a.map_or(Value::unknown(), Value::known);
+                let mut b_val = b.map_or(Value::unknown(), Value::known);
+
+                let mut last = None;
+                for row in 0..rows {
+                    // the gate looks at the next row, so the last row has no selector
+                    if row + 1 < rows {
+                        self.config.selector.enable(&mut region, row)?;
+                    }
+                    region.assign_advice(|| "a", self.config.advice[0], row, || a_val)?;
+                    last =
+                        Some(region.assign_advice(|| "b", self.config.advice[1], row, || b_val)?);
+                    (a_val, b_val) = (b_val, a_val + b_val);
+                }
+                Ok(ACell(last.unwrap()))
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// `FiboCircuit` on the two column chip, exposes the `n`-th term at row 0
+#[derive(Debug)]
+pub struct FiboCircuit2<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub n: usize,
+}
+
+impl<F: Field> Circuit<F> for FiboCircuit2<F> {
+    type Config = FiboConfig2;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: None,
+            b: None,
+            n: self.n,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        FiboChip2::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChip2::construct(config);
+        let out =
+            chip.assign_full_sequence(layouter.namespace(|| "sequence"), self.a, self.b, self.n)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cost_advice_columns, fibonacci_cost, nth_fibonacci};
+    use halo2_proofs::{
+        dev::{CircuitCost, MockProver},
+        pasta::{Eq, Fp},
+    };
+
+    fn circuit(n: usize) -> FiboCircuit2<Fp> {
+        FiboCircuit2 {
+            a: Some(Fp::one()),
+            b: Some(Fp::one()),
+            n,
+        }
+    }
+
+    #[test]
+    fn fibo2_matches_reference() {
+        for n in 2..=20 {
+            let expected = nth_fibonacci(n, Fp::one(), Fp::one());
+            MockProver::run(5, &circuit(n), vec![vec![expected]])
+                .unwrap()
+                .assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn fibo2_wrong_output_fails() {
+        let prover = MockProver::run(4, &circuit(10), vec![vec![Fp::from(56)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn fibo2_uses_fewer_columns() {
+        let cost = CircuitCost::<Eq, _>::measure(4, &circuit(10));
+        assert_eq!(cost_advice_columns(&cost), 2);
+        assert!(cost_advice_columns(&cost) < cost_advice_columns(&fibonacci_cost(4, 10)));
+    }
+}
@@ -0,0 +1,257 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// `FiboConfig` with the selector replaced by a raw fixed column holding the enable flag
+/// note: a selector is a fixed column too, but halo2 only lets it be 0 or 1 and may combine
+/// several selectors into one column. a fixed flag is assigned like any other cell,
+/// so the gate can be turned on for any set of rows, at the cost of one full fixed column
+#[derive(Clone, Debug)]
+pub struct FiboConfigFixed {
+    pub advice: [Column<Advice>; 3],
+    pub enable: Column<Fixed>,
+    pub instance: Column<Instance>,
+}
+
+pub struct FiboChipFixed<F: Field> {
+    config: FiboConfigFixed,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> FiboChipFixed<F> {
+    pub fn construct(config: FiboConfigFixed) -> Self {
+        FiboChipFixed {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> FiboConfigFixed {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let enable = meta.fixed_column();
+        let instance = meta.instance_column();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        meta.create_gate("add", |meta| {
+            // | col_a | col_b | col_c | enable |
+            // |   a   |   b   |   c   |    e   |
+            let e = meta.query_fixed(enable);
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![e * (a + b - c)]
+        });
+
+        FiboConfigFixed {
+            advice,
+            enable,
+            instance,
+        }
+    }
+
+    /// assign (a, b, c) at `offset`, with the gate on the row only if `enable`
+    /// note: unassigned fixed cells are 0, writing the 0 just keeps the flag of every row explicit
+    pub fn assign_row(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        row: [Value<F>; 3],
+        enable: bool,
+    ) -> Result<[ACell<F>; 3], Error> {
+        let flag = if enable { F::ONE } else { F::ZERO };
+        region.assign_fixed(
+            || "enable",
+            self.config.enable,
+            offset,
+            || Value::known(flag),
+        )?;
+
+        let [col_a, col_b, col_c] = self.config.advice;
+        let a = region.assign_advice(|| "a", col_a, offset, || row[0])?;
+        let b = region.assign_advice(|| "b", col_b, offset, || row[1])?;
+        let c = region.assign_advice(|| "c", col_c, offset, || row[2])?;
+        Ok([ACell(a), ACell(b), ACell(c)])
+    }
+
+    /// assign the sequence up to the `n`-th term in one region with the gate on every row,
+    /// returning the cell of the last term, same rows as `FiboChip`
+    pub fn assign_sequence(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+        n: usize,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "sequence",
+            |mut region| {
+                let a = a.map_or(Value::unknown(), Value::known);
+                let b = b.map_or(Value::unknown(), Value::known);
+                let [_, mut prev_b, mut prev_c] =
+                    self.assign_row(&mut region, 0, [a, b, a + b], true)?;
+
+                for row in 1..n.saturating_sub(2) {
+                    let c = prev_b.value().copied() + prev_c.value().copied();
+                    let [a_cell, b_cell, c_cell] = self.assign_row(
+                        &mut region,
+                        row,
+                        [prev_b.value().copied(), prev_c.value().copied(), c],
+                        true,
+                    )?;
+
+                    // copy constraint
+                    region.constrain_equal(prev_b.cell(), a_cell.cell())?;
+                    region.constrain_equal(prev_c.cell(), b_cell.cell())?;
+                    (prev_b, prev_c) = (prev_c, c_cell);
+                }
+                Ok(prev_c)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// `FiboCircuit` on the fixed column chip, exposes the `n`-th term at row 0
+#[derive(Debug)]
+pub struct FiboCircuitFixed<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub n: usize,
+}
+
+impl<F: Field> Circuit<F> for FiboCircuitFixed<F> {
+    type Config = FiboConfigFixed;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: None,
+            b: None,
+            n: self.n,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        FiboChipFixed::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = FiboChipFixed::construct(config);
+        let out =
+            chip.assign_sequence(layouter.namespace(|| "sequence"), self.a, self.b, self.n)?;
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nth_fibonacci;
+    use halo2_proofs::{
+        dev::{FailureLocation, MockProver, VerifyFailure},
+        pasta::Fp,
+    };
+
+    #[test]
+    fn fibo_fixed_matches_selector() {
+        for n in [3, 10, 20] {
+            let circuit = FiboCircuitFixed {
+                a: Some(Fp::one()),
+                b: Some(Fp::from(2)),
+                n,
+            };
+            let out = nth_fibonacci(n, Fp::one(), Fp::from(2));
+            MockProver::run(5, &circuit, vec![vec![out]])
+                .unwrap()
+                .assert_satisfied();
+            assert!(MockProver::run(5, &circuit, vec![vec![out + Fp::one()]])
+                .unwrap()
+                .verify()
+                .is_err());
+        }
+    }
+
+    // rows where only the even ones add up, the gate is on for `enabled` rows
+    struct EvenRowsCircuit {
+        enabled: fn(usize) -> bool,
+    }
+
+    impl Circuit<Fp> for EvenRowsCircuit {
+        type Config = FiboConfigFixed;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                enabled: self.enabled,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboChipFixed::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChipFixed::construct(config);
+            layouter.assign_region(
+                || "rows",
+                |mut region| {
+                    for row in 0..6 {
+                        let (a, b) = (Fp::from(row as u64), Fp::one());
+                        // odd rows are off by one
+                        let c = a + b + Fp::from(row as u64 % 2);
+                        let values = [a, b, c].map(Value::known);
+                        chip.assign_row(&mut region, row, values, (self.enabled)(row))?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn fibo_fixed_even_rows() {
+        let even = EvenRowsCircuit {
+            enabled: |row| row % 2 == 0,
+        };
+        MockProver::run(4, &even, vec![vec![]])
+            .unwrap()
+            .assert_satisfied();
+
+        // the same rows with the gate everywhere fail on the odd ones
+        let all = EvenRowsCircuit { enabled: |_| true };
+        let failures = MockProver::run(4, &all, vec![vec![]])
+            .unwrap()
+            .verify()
+            .unwrap_err();
+        let rows: Vec<_> = failures
+            .iter()
+            .map(|f| match f {
+                VerifyFailure::ConstraintNotSatisfied {
+                    location: FailureLocation::InRegion { offset, .. },
+                    ..
+                } => *offset,
+                _ => panic!("unexpected failure {f:?}"),
+            })
+            .collect();
+        assert_eq!(rows, [1, 3, 5]);
+    }
+}
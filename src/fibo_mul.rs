@@ -0,0 +1,119 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*};
+
+use crate::{FiboChip, FiboConfig, MulChip, MulConfig};
+
+/// both chips on the same three advice columns, each with its own selector
+#[derive(Clone, Debug)]
+pub struct FiboMulConfig {
+    pub fibo: FiboConfig,
+    pub mul: MulConfig,
+}
+
+/// proves `fib(n) * m`, where the sequence starts from (a, b)
+/// the product is public at `instance[0]`, the term and `m` stay private
+#[derive(Clone, Debug, Default)]
+pub struct FiboMulCircuit<F: Field> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub n: usize,
+    pub m: Option<F>,
+}
+
+impl<F: Field> Circuit<F> for FiboMulCircuit<F> {
+    type Config = FiboMulConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            n: self.n,
+            ..Default::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let selector = meta.selector();
+        FiboMulConfig {
+            fibo: FiboChip::configure(meta, advice, selector),
+            mul: MulChip::configure(meta, advice),
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // same as `FiboCircuit`, there is no term to multiply below 2
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let fibo_chip = FiboChip::construct(config.fibo);
+        let mul_chip = MulChip::construct(config.mul);
+
+        let cells = fibo_chip.assign_sequence_single_region(
+            layouter.namespace(|| "sequence"),
+            self.a,
+            self.b,
+            self.n,
+        )?;
+
+        // note: the term is copied into the mul row, otherwise a prover could multiply anything
+        let m = self.m.map_or(Value::unknown(), Value::known);
+        let product =
+            mul_chip.assign_mul_cell(layouter.namespace(|| "mul"), cells.last().unwrap(), m)?;
+
+        fibo_chip.expose_public(layouter.namespace(|| "out"), &product, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{
+        dev::{MockProver, VerifyFailure},
+        pasta::Fp,
+    };
+
+    fn fibo_10th_times_3() -> FiboMulCircuit<Fp> {
+        FiboMulCircuit {
+            a: Some(Fp::one()),
+            b: Some(Fp::one()),
+            n: 10,
+            m: Some(Fp::from(3)),
+        }
+    }
+
+    #[test]
+    fn fibo_mul_10th_times_3() {
+        // fib(10) = 55
+        MockProver::run(5, &fibo_10th_times_3(), vec![vec![Fp::from(165)]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_mul_wrong_product_fails() {
+        let prover = MockProver::run(5, &fibo_10th_times_3(), vec![vec![Fp::from(166)]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        // only the instance copy breaks, both the fibo rows and the mul row hold
+        assert!(failures
+            .iter()
+            .all(|f| matches!(f, VerifyFailure::Permutation { .. })));
+    }
+
+    #[test]
+    fn fibo_mul_too_short_is_rejected() {
+        for n in [0, 1] {
+            let circuit = FiboMulCircuit {
+                n,
+                ..fibo_10th_times_3()
+            };
+            assert!(matches!(
+                MockProver::run(5, &circuit, vec![vec![Fp::from(3)]]),
+                Err(Error::Synthesis)
+            ));
+        }
+    }
+}
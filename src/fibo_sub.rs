@@ -0,0 +1,203 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::{ACell, FirstRow};
+
+/// config of the sub chip, `FiboConfig` without the single region selector and the constants
+#[derive(Clone, Debug)]
+pub struct FiboSubConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// `FiboChip` with `c = a - b`, walks a fibonacci-like sequence backwards
+/// e.g. from (55, 34): 55, 34, 21, 13, 8, ...
+pub struct FiboSubChip<F: Field> {
+    config: FiboSubConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> FiboSubChip<F> {
+    pub fn construct(config: FiboSubConfig) -> Self {
+        FiboSubChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /// advice, selector and instance columns as `FiboChip::configure_standalone`, with the
+    /// "sub" gate. rows are chained with copy constraints like `FiboChip::assign_row`
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> FiboSubConfig {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let selector = meta.selector();
+        let instance = meta.instance_column();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        meta.create_gate("sub", |meta| {
+            // | col_a | col_b | col_c | selector |
+            // |   a   |   b   |   c   |     s    |
+            let s = meta.query_selector(selector);
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![s * (a - b - c)]
+        });
+
+        FiboSubConfig {
+            advice,
+            selector,
+            instance,
+        }
+    }
+
+    pub fn assign_first_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<FirstRow<F>, Error> {
+        layouter.assign_region(
+            || "first row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                let a_value = a.map_or(Value::unknown(), Value::known);
+                let b_value = b.map_or(Value::unknown(), Value::known);
+
+                let a_cell = region
+                    .assign_advice(|| "a", self.config.advice[0], 0, || a_value)
+                    .map(ACell)?;
+                let b_cell = region
+                    .assign_advice(|| "b", self.config.advice[1], 0, || b_value)
+                    .map(ACell)?;
+
+                // c value is a - b
+                let c_val = a_value - b_value;
+                let c_cell = region
+                    .assign_advice(|| "c", self.config.advice[2], 0, || c_val)
+                    .map(ACell)?;
+
+                Ok((a_cell, b_cell, c_cell))
+            },
+        )
+    }
+
+    pub fn assign_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        prev_b: &ACell<F>,
+        prev_c: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "next row",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                // copy constraint
+                prev_b
+                    .0
+                    .copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                prev_c
+                    .0
+                    .copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val = prev_b.value().copied() - prev_c.value().copied();
+                region
+                    .assign_advice(|| "c", self.config.advice[2], 0, || c_val)
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nth_fibonacci;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    // walks down from the 30th and 29th terms, `bump` is added to the last c
+    struct FiboSubCircuit {
+        steps: usize,
+        bump: u64,
+    }
+
+    impl Circuit<Fp> for FiboSubCircuit {
+        type Config = FiboSubConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            FiboSubCircuit {
+                steps: self.steps,
+                bump: self.bump,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboSubChip::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let fib = |n| nth_fibonacci(n, Fp::one(), Fp::one());
+            let chip = FiboSubChip::construct(config.clone());
+
+            let (_, mut prev_b, mut prev_c) = chip.assign_first_row(
+                layouter.namespace(|| "first row"),
+                Some(fib(30)),
+                Some(fib(29)),
+            )?;
+            for _ in 0..self.steps {
+                let c = chip.assign_row(layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
+                (prev_b, prev_c) = (prev_c, c);
+            }
+            // 30th, 29th, then one term down per row
+            prev_c
+                .0
+                .value()
+                .assert_if_known(|c| **c == fib(28 - self.steps));
+
+            // one more row with a wrong c, which only the gate can catch
+            if self.bump > 0 {
+                layouter.assign_region(
+                    || "bad row",
+                    |mut region| {
+                        config.selector.enable(&mut region, 0)?;
+                        prev_b
+                            .0
+                            .copy_advice(|| "a", &mut region, config.advice[0], 0)?;
+                        prev_c
+                            .0
+                            .copy_advice(|| "b", &mut region, config.advice[1], 0)?;
+                        let c = prev_b.value().copied() - prev_c.value().copied()
+                            + Value::known(Fp::from(self.bump));
+                        region.assign_advice(|| "c", config.advice[2], 0, || c)?;
+                        Ok(())
+                    },
+                )?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fibo_sub_walks_down() {
+        let circuit = FiboSubCircuit { steps: 20, bump: 0 };
+        MockProver::run(5, &circuit, vec![vec![]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_sub_off_by_one_fails() {
+        let circuit = FiboSubCircuit { steps: 20, bump: 1 };
+        let prover = MockProver::run(5, &circuit, vec![vec![]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
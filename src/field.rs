@@ -0,0 +1,110 @@
+use halo2_proofs::pasta::group::ff::PrimeField;
+
+use crate::FiboError;
+
+/// field element from a decimal or `0x` prefixed hex string, e.g. "55" or "0x37"
+/// note: builds the repr byte by byte, so it assumes a little endian repr like pasta and bn256
+pub fn parse_field<F: PrimeField>(s: &str) -> Result<F, FiboError> {
+    let (digits, radix) = match s.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (s, 10),
+    };
+    if digits.is_empty() {
+        return Err(FiboError::InvalidScalar(s.to_string()));
+    }
+    let out_of_range = || FiboError::ScalarOutOfRange {
+        value: s.to_string(),
+        modulus: F::MODULUS.to_string(),
+    };
+
+    // repr = repr * radix + digit, carried through the bytes
+    let mut repr = F::Repr::default();
+    for c in digits.chars() {
+        let mut carry = c
+            .to_digit(radix)
+            .ok_or_else(|| FiboError::InvalidScalar(s.to_string()))?;
+        for byte in repr.as_mut().iter_mut() {
+            let cur = *byte as u32 * radix + carry;
+            *byte = cur as u8;
+            carry = cur >> 8;
+        }
+        if carry != 0 {
+            return Err(out_of_range());
+        }
+    }
+    // fits in the repr, but can still be >= p
+    Option::from(F::from_repr(repr)).ok_or_else(out_of_range)
+}
+
+/// decimal string of a field element, e.g. "55"
+pub fn to_decimal<F: PrimeField>(value: &F) -> String {
+    // big endian bytes, divided by 10 until it's zero
+    let mut bytes: Vec<u8> = value.to_repr().as_ref().iter().rev().copied().collect();
+    let mut digits = vec![];
+    while bytes.iter().any(|&b| b != 0) {
+        let mut rem = 0u16;
+        for byte in bytes.iter_mut() {
+            let cur = (rem << 8) | *byte as u16;
+            *byte = (cur / 10) as u8;
+            rem = cur % 10;
+        }
+        digits.push(b'0' + rem as u8);
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::pasta::Fp;
+
+    #[test]
+    fn parse_field_decimal() {
+        assert_eq!(parse_field::<Fp>("55").unwrap(), Fp::from(55));
+        assert_eq!(parse_field::<Fp>("007").unwrap(), Fp::from(7));
+        assert_eq!(parse_field::<Fp>("0").unwrap(), Fp::zero());
+        // full width, past u64
+        let big = "18446744073709551616";
+        assert_eq!(
+            parse_field::<Fp>(big).unwrap(),
+            Fp::from(u64::MAX) + Fp::one()
+        );
+        assert_eq!(to_decimal(&parse_field::<Fp>(big).unwrap()), big);
+    }
+
+    #[test]
+    fn parse_field_hex() {
+        assert_eq!(parse_field::<Fp>("0x37").unwrap(), Fp::from(55));
+        assert_eq!(parse_field::<Fp>("0xfF").unwrap(), Fp::from(255));
+        // p - 1
+        let modulus = Fp::MODULUS;
+        let max = format!("{}0", &modulus[..modulus.len() - 1]);
+        assert_eq!(parse_field::<Fp>(&max).unwrap(), -Fp::one());
+    }
+
+    #[test]
+    fn parse_field_out_of_range() {
+        let parse = parse_field::<Fp>;
+        // p in hex and decimal, then one past the 32 byte repr
+        let p_decimal =
+            "28948022309329048855892746252171976963363056481941560715954676764349967630337";
+        let wide = format!("0x1{}", "0".repeat(64));
+        for big in [Fp::MODULUS, p_decimal, wide.as_str()] {
+            assert!(
+                matches!(parse(big), Err(FiboError::ScalarOutOfRange { .. })),
+                "{big}"
+            );
+        }
+
+        for bad in ["", "0x", "-1", "1.5", "0xg", "12a"] {
+            assert!(
+                matches!(parse(bad), Err(FiboError::InvalidScalar(_))),
+                "{bad:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// config of the is_zero gadget: value, its inverse witness and the output
+#[derive(Clone, Debug)]
+pub struct IsZeroConfig {
+    pub value: Column<Advice>,
+    pub inv: Column<Advice>,
+    pub is_zero: Column<Advice>,
+    pub selector: Selector,
+}
+
+/// outputs 1 if the value is zero, 0 otherwise
+pub struct IsZeroChip<F: Field> {
+    config: IsZeroConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> IsZeroChip<F> {
+    pub fn construct(config: IsZeroConfig) -> Self {
+        IsZeroChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> IsZeroConfig {
+        let value = meta.advice_column();
+        let inv = meta.advice_column();
+        let is_zero = meta.advice_column();
+        let selector = meta.selector();
+
+        meta.enable_equality(value);
+        meta.enable_equality(is_zero);
+
+        meta.create_gate("is zero", |meta| {
+            // | value | inv | is_zero | selector |
+            // |   v   |  i  |    z    |     s    |
+            let s = meta.query_selector(selector);
+            let v = meta.query_advice(value, Rotation::cur());
+            let i = meta.query_advice(inv, Rotation::cur());
+            let z = meta.query_advice(is_zero, Rotation::cur());
+
+            // 1 - v * i is 1 when v = 0, and 0 when i is the inverse of v
+            let one_minus_vi = Expression::Constant(F::ONE) - v.clone() * i;
+
+            vec![
+                // v = 0 or v * i = 1, so the prover can't pick i freely
+                s.clone() * v * one_minus_vi.clone(),
+                s * (z - one_minus_vi),
+            ]
+        });
+
+        IsZeroConfig {
+            value,
+            inv,
+            is_zero,
+            selector,
+        }
+    }
+
+    pub fn assign(&self, layouter: impl Layouter<F>, value: Value<F>) -> Result<ACell<F>, Error> {
+        self.assign_with(layouter, value, |region, column| {
+            region.assign_advice(|| "value", column, 0, || value)?;
+            Ok(())
+        })
+    }
+
+    /// same as `assign`, with the value copied in from `cell`
+    pub fn assign_cell(
+        &self,
+        layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        self.assign_with(layouter, cell.value().copied(), |region, column| {
+            // copy constraint
+            cell.as_ref().copy_advice(|| "value", region, column, 0)?;
+            Ok(())
+        })
+    }
+
+    // `assign_value` puts `value` in the value column, the witnesses are derived
+    // from it in constant time
+    fn assign_with(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Value<F>,
+        assign_value: impl Fn(&mut Region<'_, F>, Column<Advice>) -> Result<(), Error>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "is zero",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                // inverse of 0 is taken as 0
+                let inv = value.map(|v| v.invert().unwrap_or(F::ZERO));
+                let is_zero = value.map(|v| F::conditional_select(&F::ZERO, &F::ONE, v.is_zero()));
+
+                assign_value(&mut region, self.config.value)?;
+                region.assign_advice(|| "inv", self.config.inv, 0, || inv)?;
+                region
+                    .assign_advice(|| "is zero", self.config.is_zero, 0, || is_zero)
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    struct IsZeroCircuit {
+        value: Value<Fp>,
+        expected: Fp,
+    }
+
+    impl Circuit<Fp> for IsZeroCircuit {
+        type Config = IsZeroConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                value: Value::unknown(),
+                expected: self.expected,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            IsZeroChip::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = IsZeroChip::construct(config);
+            let out = chip.assign(layouter.namespace(|| "value"), self.value)?;
+            out.value().assert_if_known(|z| **z == self.expected);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn is_zero_of_zero() {
+        let circuit = IsZeroCircuit {
+            value: Value::known(Fp::ZERO),
+            expected: Fp::ONE,
+        };
+        MockProver::run(4, &circuit, vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn is_zero_of_five() {
+        let circuit = IsZeroCircuit {
+            value: Value::known(Fp::from(5)),
+            expected: Fp::ZERO,
+        };
+        MockProver::run(4, &circuit, vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+}
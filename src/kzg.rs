@@ -0,0 +1,320 @@
+//! KZG over bn256 backend, enabled by the `kzg` feature
+//! note: zcash halo2 only ships IPA, so this side is built on the axiom fork of halo2.
+//! the circuit is the same `FiboCircuit` (same advice columns and "add" gate), implemented for
+//! the fork's traits. it has no "next row" gate and no constants column, rows are chained by
+//! copy constraints like `FiboChip::assign_full_sequence`
+//! verifying key caching lives here too, zcash halo2 has no way to serialize a verifying key
+use halo2_axiom::{
+    circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value},
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::{
+        create_proof, keygen_pk, keygen_vk, verify_proof, Advice, Assigned, Circuit, Column,
+        ConstraintSystem, Error, Instance, Selector, VerifyingKey,
+    },
+    poly::{
+        commitment::Params,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{ProverSHPLONK, VerifierSHPLONK},
+            strategy::SingleStrategy,
+        },
+        Rotation,
+    },
+    transcript::{
+        Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+    },
+    SerdeFormat,
+};
+use rand_core::OsRng;
+use std::{fs, io, path::Path};
+
+use crate::{FiboCircuit, FiboError};
+
+/// same columns as `FiboConfig`, but typed for the KZG backend
+#[derive(Clone, Debug)]
+pub struct FiboConfigKzg {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+impl Circuit<Fr> for FiboCircuit<Fr> {
+    type Config = FiboConfigKzg;
+    type FloorPlanner = SimpleFloorPlanner;
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self::empty(self.n)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let selector = meta.selector();
+        let instance = meta.instance_column();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        // | col_a | col_b | col_c | selector |
+        // |   a   |   b   |   c   |     s    |
+        meta.create_gate("add", |meta| {
+            let s = meta.query_selector(selector);
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![s * (a + b - c)]
+        });
+
+        FiboConfigKzg {
+            advice,
+            selector,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        // same as the IPA circuit, n = 1 would only expose the seed's a
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let cells = self.assign_sequence_kzg(&config, layouter.namespace(|| "fibonacci"))?;
+        layouter.constrain_instance(cells.last().unwrap().cell(), config.instance, 0);
+        Ok(())
+    }
+}
+
+/// assigned cell of the KZG backend, the fork hands out references to the assigned values
+pub type ACellKzg<'v, F> = AssignedCell<&'v Assigned<F>, F>;
+
+impl FiboCircuit<Fr> {
+    /// assign the whole sequence up to the `n`-th term, returning the cells of all terms in order
+    /// note: same as `FiboChip::assign_full_sequence`, for reuse by other KZG circuits
+    pub fn assign_sequence_kzg<'v>(
+        &self,
+        config: &FiboConfigKzg,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<Vec<ACellKzg<'v, Fr>>, Error> {
+        let [col_a, col_b, col_c] = config.advice;
+
+        // note: the axiom fork lays regions out for a single region per circuit,
+        // so all rows go into one region and row i copies b, c of row i - 1 into a, b
+        layouter.assign_region(
+            || "fibonacci",
+            |mut region| {
+                // first row: a, b are witnesses and c = a + b
+                config.selector.enable(&mut region, 0)?;
+                let a = self.a.map_or(Value::unknown(), Value::known);
+                let b = self.b.map_or(Value::unknown(), Value::known);
+                let a_cell = region.assign_advice(col_a, 0, a);
+                let mut prev_b = region.assign_advice(col_b, 0, b);
+                let mut prev_c = region.assign_advice(col_c, 0, a.zip(b).map(|(a, b)| a + b));
+                let mut cells = vec![a_cell, prev_b.clone(), prev_c.clone()];
+
+                for row in 1..self.n.saturating_sub(2) {
+                    config.selector.enable(&mut region, row)?;
+
+                    prev_b.copy_advice(&mut region, col_a, row);
+                    prev_c.copy_advice(&mut region, col_b, row);
+                    let c_val = prev_b.value().zip(prev_c.value()).map(|(b, c)| *b + *c);
+                    let c_cell = region.assign_advice(col_c, row, c_val);
+                    cells.push(c_cell.clone());
+
+                    prev_b = prev_c;
+                    prev_c = c_cell;
+                }
+                // the first row always holds 3 terms, drop the 3rd one when n = 2
+                cells.truncate(self.n);
+                Ok(cells)
+            },
+        )
+    }
+}
+
+/// KZG params for circuit size `k`
+/// note: unlike IPA this is a trusted setup, the prover and verifier must share the same params.
+/// random setup is fine for testing, production should load params from a ceremony
+pub fn params_for_kzg(k: u32) -> ParamsKZG<Bn256> {
+    ParamsKZG::setup(k, OsRng)
+}
+
+/// `prove_fibonacci` for the KZG backend, proving with SHPLONK multiopen
+/// takes the params instead of `k` since KZG params can't be regenerated from `k`,
+/// `public_inputs[i]` holds the rows of instance column `i` as in `verify_fibonacci`
+pub fn prove_fibonacci_kzg(
+    params: &ParamsKZG<Bn256>,
+    circuit: FiboCircuit<Fr>,
+    public_inputs: &[&[Fr]],
+) -> Result<Vec<u8>, FiboError> {
+    let vk = keygen_vk(params, &circuit.without_witnesses())?;
+    let pk = keygen_pk(params, vk, &circuit.without_witnesses())?;
+
+    let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<'_, Bn256>, _, _, _, _>(
+        params,
+        &pk,
+        &[circuit],
+        &[public_inputs],
+        OsRng,
+        &mut transcript,
+    )?;
+    Ok(transcript.finalize())
+}
+
+/// `verify_fibonacci` for the KZG backend, with the public inputs grouped per instance column
+pub fn verify_fibonacci_kzg(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    public_inputs: &[&[Fr]],
+    proof: &[u8],
+) -> Result<(), FiboError> {
+    let strategy = SingleStrategy::new(params);
+    let mut transcript = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(proof);
+    verify_proof::<KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'_, Bn256>, _, _, _>(
+        params,
+        vk,
+        strategy,
+        &[public_inputs],
+        &mut transcript,
+    )?;
+    Ok(())
+}
+
+/// write the verifying key to `path`
+pub fn write_vk_kzg(vk: &VerifyingKey<G1Affine>, path: impl AsRef<Path>) -> Result<(), FiboError> {
+    let mut bytes = vec![];
+    vk.write(&mut bytes, SerdeFormat::Processed)?;
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// read a verifying key written by `write_vk_kzg`, rejecting one made for params of another `k`
+/// note: the file only holds commitments, the constraint system comes from `FiboCircuit` itself,
+/// so it must be read for the same `n` it was generated for
+pub fn read_vk_kzg(
+    path: impl AsRef<Path>,
+    params: &ParamsKZG<Bn256>,
+) -> Result<VerifyingKey<G1Affine>, FiboError> {
+    let path = path.as_ref();
+    let bytes = fs::read(path)?;
+    check_vk_k(&bytes, params, path)?;
+    Ok(VerifyingKey::read::<_, FiboCircuit<Fr>>(
+        &mut &bytes[..],
+        SerdeFormat::Processed,
+        (),
+    )?)
+}
+
+/// read the cached vk at `path`, or run keygen and write the cache if it is missing
+/// or can't be parsed (e.g. written by another version), a vk for another `k` is still an error
+pub fn load_or_generate_vk_kzg(
+    params: &ParamsKZG<Bn256>,
+    circuit: &FiboCircuit<Fr>,
+    path: impl AsRef<Path>,
+) -> Result<VerifyingKey<G1Affine>, FiboError> {
+    let path = path.as_ref();
+    if path.exists() {
+        let bytes = fs::read(path)?;
+        // don't silently overwrite a cache meant for another circuit size
+        check_vk_k(&bytes, params, path)?;
+        if let Ok(vk) =
+            VerifyingKey::read::<_, FiboCircuit<Fr>>(&mut &bytes[..], SerdeFormat::Processed, ())
+        {
+            return Ok(vk);
+        }
+    }
+
+    let vk = keygen_vk(params, &circuit.without_witnesses())?;
+    write_vk_kzg(&vk, path)?;
+    Ok(vk)
+}
+
+// vk layout starts with a version byte, then k
+fn check_vk_k(bytes: &[u8], params: &ParamsKZG<Bn256>, path: &Path) -> Result<(), FiboError> {
+    let cached_k = bytes
+        .get(1..5)
+        .map(|k| u32::from_le_bytes(k.try_into().unwrap()));
+    if cached_k != Some(params.k()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "cached vk at {} is for k = {cached_k:?}, params are for k = {}",
+                path.display(),
+                params.k()
+            ),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::marker::PhantomData;
+
+    #[test]
+    fn fibo_kzg_prove_and_verify() {
+        let params = params_for_kzg(4);
+        let circuit = FiboCircuit {
+            a: Some(Fr::from(1)),
+            b: Some(Fr::from(1)),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+
+        let proof = prove_fibonacci_kzg(&params, circuit, &[&[Fr::from(55)]]).unwrap();
+        assert!(verify_fibonacci_kzg(&params, &vk, &[&[Fr::from(55)]], &proof).is_ok());
+        assert!(verify_fibonacci_kzg(&params, &vk, &[&[Fr::from(56)]], &proof).is_err());
+    }
+
+    #[test]
+    fn fibo_kzg_vk_cache_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vk_k4.bin");
+        let params = params_for_kzg(4);
+        let circuit = FiboCircuit {
+            a: Some(Fr::from(1)),
+            b: Some(Fr::from(1)),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+
+        // first call runs keygen and writes, then the cache is read back
+        load_or_generate_vk_kzg(&params, &circuit, &path).unwrap();
+        let vk = read_vk_kzg(&path, &params).unwrap();
+
+        let proof = prove_fibonacci_kzg(&params, circuit, &[&[Fr::from(55)]]).unwrap();
+        assert!(verify_fibonacci_kzg(&params, &vk, &[&[Fr::from(55)]], &proof).is_ok());
+
+        // cache for another k is rejected
+        let err = read_vk_kzg(&path, &params_for_kzg(5)).unwrap_err();
+        assert!(matches!(err, FiboError::Io(e) if e.kind() == io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn fibo_kzg_too_short_is_rejected() {
+        let params = params_for_kzg(4);
+        for n in [0, 1] {
+            let circuit = FiboCircuit {
+                a: Some(Fr::from(1)),
+                b: Some(Fr::from(1)),
+                n,
+                output: None,
+                planner: PhantomData,
+            };
+            assert!(matches!(
+                prove_fibonacci_kzg(&params, circuit, &[&[Fr::from(1)]]),
+                Err(FiboError::Kzg(Error::Synthesis))
+            ));
+            assert!(keygen_vk(&params, &FiboCircuit::<Fr>::empty(n)).is_err());
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! halo2 fibonacci circuit, shared by the example binary, tests and benches
+//! note: without the `std` feature only the core chip is built, on `no_std + alloc`
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+mod accumulate;
+#[cfg(feature = "std")]
+mod bits;
+#[cfg(feature = "std")]
+mod cost;
+#[cfg(feature = "std")]
+mod equality;
+mod error;
+mod fibo;
+#[cfg(feature = "std")]
+mod fibo2;
+#[cfg(feature = "std")]
+mod fibo_fixed;
+#[cfg(feature = "std")]
+mod fibo_mul;
+#[cfg(feature = "std")]
+mod fibo_sub;
+#[cfg(feature = "std")]
+mod field;
+#[cfg(feature = "std")]
+mod is_zero;
+#[cfg(feature = "kzg")]
+mod kzg;
+#[cfg(feature = "std")]
+mod linear_rec;
+#[cfg(feature = "std")]
+mod lt;
+#[cfg(feature = "std")]
+mod merkle;
+#[cfg(feature = "std")]
+mod modulo;
+#[cfg(feature = "std")]
+mod monotonic;
+#[cfg(feature = "std")]
+mod mul;
+#[cfg(feature = "serde")]
+mod params;
+#[cfg(feature = "dev-graph")]
+mod plot;
+#[cfg(feature = "std")]
+mod poseidon;
+#[cfg(feature = "std")]
+mod proof;
+#[cfg(feature = "std")]
+mod range_check;
+#[cfg(feature = "std")]
+mod range_table;
+#[cfg(feature = "kzg")]
+mod rlc;
+#[cfg(feature = "circuit-scanner")]
+mod scanner;
+#[cfg(feature = "std")]
+mod select;
+#[cfg(feature = "solidity")]
+mod solidity;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "std")]
+mod wide;
+#[cfg(feature = "std")]
+mod xor;
+
+#[cfg(feature = "std")]
+pub use accumulate::*;
+#[cfg(feature = "std")]
+pub use bits::*;
+#[cfg(feature = "std")]
+pub use cost::*;
+#[cfg(feature = "std")]
+pub use equality::*;
+pub use error::*;
+pub use fibo::*;
+#[cfg(feature = "std")]
+pub use fibo2::*;
+#[cfg(feature = "std")]
+pub use fibo_fixed::*;
+#[cfg(feature = "std")]
+pub use fibo_mul::*;
+#[cfg(feature = "std")]
+pub use fibo_sub::*;
+#[cfg(feature = "std")]
+pub use field::*;
+#[cfg(feature = "std")]
+pub use is_zero::*;
+#[cfg(feature = "kzg")]
+pub use kzg::*;
+#[cfg(feature = "std")]
+pub use linear_rec::*;
+#[cfg(feature = "std")]
+pub use lt::*;
+#[cfg(feature = "std")]
+pub use merkle::*;
+#[cfg(feature = "std")]
+pub use modulo::*;
+#[cfg(feature = "std")]
+pub use monotonic::*;
+#[cfg(feature = "std")]
+pub use mul::*;
+#[cfg(feature = "serde")]
+pub use params::*;
+#[cfg(feature = "dev-graph")]
+pub use plot::*;
+#[cfg(feature = "std")]
+pub use poseidon::*;
+#[cfg(feature = "std")]
+pub use proof::*;
+#[cfg(feature = "std")]
+pub use range_check::*;
+#[cfg(feature = "std")]
+pub use range_table::*;
+#[cfg(feature = "kzg")]
+pub use rlc::*;
+#[cfg(feature = "circuit-scanner")]
+pub use scanner::*;
+#[cfg(feature = "std")]
+pub use select::*;
+#[cfg(feature = "solidity")]
+pub use solidity::*;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
+#[cfg(feature = "std")]
+pub use wide::*;
+#[cfg(feature = "std")]
+pub use xor::*;
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    // the core chip has to keep building without `std`
+    // note: own target dir, the outer `cargo test` may still hold the lock of the default one
+    #[test]
+    fn builds_without_std() {
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        let status = Command::new(env!("CARGO"))
+            .args(["check", "--lib", "--no-default-features", "--quiet"])
+            .arg("--manifest-path")
+            .arg(format!("{manifest_dir}/Cargo.toml"))
+            .arg("--target-dir")
+            .arg(format!("{manifest_dir}/target/no_std"))
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+}
@@ -0,0 +1,265 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+
+use crate::{ACell, FirstRow};
+
+/// config of `c = p * a + q * b`, the coefficients live in fixed columns
+#[derive(Clone, Debug)]
+pub struct LinearRecConfig {
+    pub advice: [Column<Advice>; 3],
+    pub coeffs: [Column<Fixed>; 2],
+    pub selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// generalized `FiboChip` for linear recurrences, fibonacci is p = q = 1
+pub struct LinearRecChip<F: Field> {
+    config: LinearRecConfig,
+    p: F,
+    q: F,
+}
+
+impl<F: Field> LinearRecChip<F> {
+    pub fn construct(config: LinearRecConfig, p: F, q: F) -> Self {
+        LinearRecChip { config, p, q }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> LinearRecConfig {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let coeffs = [(); 2].map(|_| meta.fixed_column());
+        let selector = meta.selector();
+        let instance = meta.instance_column();
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        meta.create_gate("linear recurrence", |meta| {
+            // | col_a | col_b | col_c | col_p | col_q | selector |
+            // |   a   |   b   |   c   |   p   |   q   |     s    |
+            let s = meta.query_selector(selector);
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            let [p, q] = coeffs.map(|column| meta.query_fixed(column));
+            vec![s * (p * a + q * b - c)]
+        });
+
+        LinearRecConfig {
+            advice,
+            coeffs,
+            selector,
+            instance,
+        }
+    }
+
+    // enable the gate on `row` and pin the coefficients there
+    fn enable_row(&self, region: &mut Region<'_, F>, row: usize) -> Result<(), Error> {
+        self.config.selector.enable(region, row)?;
+        region.assign_fixed(|| "p", self.config.coeffs[0], row, || Value::known(self.p))?;
+        region.assign_fixed(|| "q", self.config.coeffs[1], row, || Value::known(self.q))?;
+        Ok(())
+    }
+
+    pub fn assign_first_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+    ) -> Result<FirstRow<F>, Error> {
+        layouter.assign_region(
+            || "first row",
+            |mut region| {
+                self.enable_row(&mut region, 0)?;
+
+                let a_value = a.map_or(Value::unknown(), Value::known);
+                let b_value = b.map_or(Value::unknown(), Value::known);
+
+                let a_cell = region
+                    .assign_advice(|| "a", self.config.advice[0], 0, || a_value)
+                    .map(ACell)?;
+                let b_cell = region
+                    .assign_advice(|| "b", self.config.advice[1], 0, || b_value)
+                    .map(ACell)?;
+
+                let c_val = a_value.zip(b_value).map(|(a, b)| self.p * a + self.q * b);
+                let c_cell = region
+                    .assign_advice(|| "c", self.config.advice[2], 0, || c_val)
+                    .map(ACell)?;
+
+                Ok((a_cell, b_cell, c_cell))
+            },
+        )
+    }
+
+    pub fn assign_row(
+        &self,
+        mut layouter: impl Layouter<F>,
+        prev_b: &ACell<F>,
+        prev_c: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "next row",
+            |mut region| {
+                self.enable_row(&mut region, 0)?;
+
+                let a = prev_b
+                    .0
+                    .copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                let b = prev_c
+                    .0
+                    .copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let c_val = a
+                    .value()
+                    .zip(b.value())
+                    .map(|(a, b)| self.p * a + self.q * b);
+                region
+                    .assign_advice(|| "c", self.config.advice[2], 0, || c_val)
+                    .map(ACell)
+            },
+        )
+    }
+
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+/// `FiboCircuit` for `c = p * a + q * b`, exposes the `n`-th term at row 0
+/// e.g. Lucas numbers are p = q = 1 from (2, 1), Pell numbers are p = 1, q = 2 from (0, 1)
+#[derive(Debug)]
+pub struct LinearRecCircuit<F> {
+    pub p: F,
+    pub q: F,
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub n: usize,
+}
+
+impl<F: Field> Circuit<F> for LinearRecCircuit<F> {
+    type Config = LinearRecConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        // coefficients are fixed columns, so they stay
+        Self {
+            p: self.p,
+            q: self.q,
+            a: None,
+            b: None,
+            n: self.n,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        LinearRecChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // same as `FiboCircuit`, n = 1 would expose the seed's a as the output
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let chip = LinearRecChip::construct(config, self.p, self.q);
+
+        let (_, mut prev_b, mut prev_c) =
+            chip.assign_first_row(layouter.namespace(|| "first row"), self.a, self.b)?;
+        for _i in 3..self.n {
+            let c = chip.assign_row(layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
+            (prev_b, prev_c) = (prev_c, c);
+        }
+
+        let out = if self.n == 2 { prev_b } else { prev_c };
+        chip.expose_public(layouter.namespace(|| "out"), &out, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    // reference n-th term for n >= 2, `a` is the 1st term
+    fn nth_term(n: usize, p: u64, q: u64, a: u64, b: u64) -> u64 {
+        let (mut prev, mut cur) = (a, b);
+        for _ in 2..n {
+            (prev, cur) = (cur, p * prev + q * cur);
+        }
+        cur
+    }
+
+    fn check(p: u64, q: u64, a: u64, b: u64) {
+        for n in 2..=20 {
+            let circuit = LinearRecCircuit {
+                p: Fp::from(p),
+                q: Fp::from(q),
+                a: Some(Fp::from(a)),
+                b: Some(Fp::from(b)),
+                n,
+            };
+            let expected = Fp::from(nth_term(n, p, q, a, b));
+            MockProver::run(5, &circuit, vec![vec![expected]])
+                .unwrap()
+                .assert_satisfied();
+        }
+    }
+
+    #[test]
+    fn linear_rec_fibonacci() {
+        check(1, 1, 1, 1);
+        assert_eq!(nth_term(10, 1, 1, 1, 1), 55);
+    }
+
+    #[test]
+    fn linear_rec_pell() {
+        // 0, 1, 2, 5, 12, 29, ...
+        check(1, 2, 0, 1);
+        assert_eq!(nth_term(6, 1, 2, 0, 1), 29);
+    }
+
+    #[test]
+    fn linear_rec_lucas() {
+        // 2, 1, 3, 4, 7, 11, ...
+        check(1, 1, 2, 1);
+        assert_eq!(nth_term(6, 1, 1, 2, 1), 11);
+    }
+
+    #[test]
+    fn linear_rec_wrong_output_fails() {
+        let circuit = LinearRecCircuit {
+            p: Fp::from(1),
+            q: Fp::from(2),
+            a: Some(Fp::from(0)),
+            b: Some(Fp::from(1)),
+            n: 6,
+        };
+        let prover = MockProver::run(5, &circuit, vec![vec![Fp::from(30)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn linear_rec_too_short_is_rejected() {
+        for n in [0, 1] {
+            let circuit = LinearRecCircuit {
+                p: Fp::from(1),
+                q: Fp::from(1),
+                a: Some(Fp::from(1)),
+                b: Some(Fp::from(1)),
+                n,
+            };
+            assert!(matches!(
+                MockProver::run(5, &circuit, vec![vec![Fp::from(1)]]),
+                Err(Error::Synthesis)
+            ));
+        }
+    }
+}
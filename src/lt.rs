@@ -0,0 +1,267 @@
+use halo2_proofs::{circuit::*, pasta::group::ff::PrimeField, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// config of the less-than comparator
+/// `diff` holds the little-endian bytes of `lhs - rhs + lt * 2^(8 * N_BYTES)`
+#[derive(Clone, Debug)]
+pub struct LtConfig<const N_BYTES: usize> {
+    pub lhs: Column<Advice>,
+    pub rhs: Column<Advice>,
+    pub lt: Column<Advice>,
+    pub diff: [Column<Advice>; N_BYTES],
+    pub selector: Selector,
+    pub u8_table: TableColumn,
+}
+
+/// outputs 1 if `lhs < rhs`, 0 otherwise
+/// note: both sides must be less than 2^(8 * N_BYTES) apart, otherwise the diff doesn't fit the
+/// bytes and the proof is unsatisfied
+pub struct LtChip<F: PrimeField, const N_BYTES: usize> {
+    config: LtConfig<N_BYTES>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: PrimeField, const N_BYTES: usize> LtChip<F, N_BYTES> {
+    pub fn construct(config: LtConfig<N_BYTES>) -> Self {
+        LtChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> LtConfig<N_BYTES> {
+        let lhs = meta.advice_column();
+        let rhs = meta.advice_column();
+        let lt = meta.advice_column();
+        let diff = [(); N_BYTES].map(|_| meta.advice_column());
+        let selector = meta.complex_selector();
+        let u8_table = meta.lookup_table_column();
+
+        meta.enable_equality(lhs);
+        meta.enable_equality(rhs);
+        meta.enable_equality(lt);
+
+        meta.create_gate("lt", |meta| {
+            // | lhs | rhs | lt | diff[0] .. diff[N_BYTES - 1] | selector |
+            let s = meta.query_selector(selector);
+            let lhs = meta.query_advice(lhs, Rotation::cur());
+            let rhs = meta.query_advice(rhs, Rotation::cur());
+            let lt = meta.query_advice(lt, Rotation::cur());
+
+            // sum of diff[i] * 256^i
+            let diff = diff
+                .iter()
+                .rev()
+                .fold(Expression::Constant(F::ZERO), |acc, column| {
+                    acc * Expression::Constant(F::from(256))
+                        + meta.query_advice(*column, Rotation::cur())
+                });
+            let range = Expression::Constant(range::<F, N_BYTES>());
+
+            vec![
+                // lt is a boolean
+                s.clone() * lt.clone() * (Expression::Constant(F::ONE) - lt.clone()),
+                // lhs - rhs + lt * 2^(8 * N_BYTES) fits in N_BYTES bytes
+                s * (lhs - rhs + lt * range - diff),
+            ]
+        });
+
+        // every diff byte is in 0..256
+        for column in diff {
+            meta.lookup(|meta| {
+                let s = meta.query_selector(selector);
+                let byte = meta.query_advice(column, Rotation::cur());
+                vec![(s * byte, u8_table)]
+            });
+        }
+
+        LtConfig {
+            lhs,
+            rhs,
+            lt,
+            diff,
+            selector,
+            u8_table,
+        }
+    }
+
+    /// fill the 0..256 lookup table, needs to run once per circuit
+    pub fn load_table(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "u8 table",
+            |mut table| {
+                for i in 0..256 {
+                    table.assign_cell(
+                        || "u8",
+                        self.config.u8_table,
+                        i,
+                        || Value::known(F::from(i as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        lhs: Value<F>,
+        rhs: Value<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "lt",
+            |mut region| {
+                let [_, _, lt] = self.assign_row(&mut region, lhs, rhs)?;
+                Ok(lt)
+            },
+        )
+    }
+
+    /// same as `assign`, with both sides copied in from cells
+    pub fn assign_cells(
+        &self,
+        mut layouter: impl Layouter<F>,
+        lhs: &ACell<F>,
+        rhs: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "lt",
+            |mut region| {
+                let [lhs_cell, rhs_cell, lt] =
+                    self.assign_row(&mut region, lhs.value().copied(), rhs.value().copied())?;
+
+                // copy constraint
+                region.constrain_equal(lhs.cell(), lhs_cell.cell())?;
+                region.constrain_equal(rhs.cell(), rhs_cell.cell())?;
+                Ok(lt)
+            },
+        )
+    }
+
+    // [lhs, rhs, lt] cells of the row
+    fn assign_row(
+        &self,
+        region: &mut Region<'_, F>,
+        lhs: Value<F>,
+        rhs: Value<F>,
+    ) -> Result<[ACell<F>; 3], Error> {
+        self.config.selector.enable(region, 0)?;
+
+        let lhs_cell = region.assign_advice(|| "lhs", self.config.lhs, 0, || lhs)?;
+        let rhs_cell = region.assign_advice(|| "rhs", self.config.rhs, 0, || rhs)?;
+
+        let lt = lhs.zip(rhs).map(|(lhs, rhs)| is_less(lhs, rhs));
+        let diff = lhs
+            .zip(rhs)
+            .zip(lt)
+            .map(|((lhs, rhs), lt)| lhs - rhs + if lt { range::<F, N_BYTES>() } else { F::ZERO });
+
+        // note: bytes past N_BYTES are dropped, an out of range diff fails the gate
+        for (i, column) in self.config.diff.iter().enumerate() {
+            let byte = diff.map(|diff| F::from(diff.to_repr().as_ref()[i] as u64));
+            region.assign_advice(|| "diff byte", *column, 0, || byte)?;
+        }
+
+        let lt_cell = region.assign_advice(
+            || "lt",
+            self.config.lt,
+            0,
+            || lt.map(|lt| if lt { F::ONE } else { F::ZERO }),
+        )?;
+        Ok([ACell(lhs_cell), ACell(rhs_cell), ACell(lt_cell)])
+    }
+}
+
+// 2^(8 * N_BYTES)
+fn range<F: PrimeField, const N_BYTES: usize>() -> F {
+    F::from(256).pow_vartime([N_BYTES as u64])
+}
+
+// compare as integers, assumes a little-endian repr (true for the pasta fields)
+fn is_less<F: PrimeField>(lhs: F, rhs: F) -> bool {
+    let (lhs, rhs) = (lhs.to_repr(), rhs.to_repr());
+    lhs.as_ref().iter().rev().lt(rhs.as_ref().iter().rev())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    struct LtCircuit<const N_BYTES: usize> {
+        lhs: Value<Fp>,
+        rhs: Value<Fp>,
+        expected: Fp,
+    }
+
+    impl<const N_BYTES: usize> Circuit<Fp> for LtCircuit<N_BYTES> {
+        type Config = LtConfig<N_BYTES>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                lhs: Value::unknown(),
+                rhs: Value::unknown(),
+                expected: self.expected,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            LtChip::<Fp, N_BYTES>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = LtChip::<Fp, N_BYTES>::construct(config);
+            chip.load_table(layouter.namespace(|| "table"))?;
+            let lt = chip.assign(layouter.namespace(|| "lt"), self.lhs, self.rhs)?;
+            lt.value().assert_if_known(|lt| **lt == self.expected);
+            Ok(())
+        }
+    }
+
+    fn lt_circuit<const N_BYTES: usize>(lhs: u64, rhs: u64, expected: bool) -> LtCircuit<N_BYTES> {
+        LtCircuit {
+            lhs: Value::known(Fp::from(lhs)),
+            rhs: Value::known(Fp::from(rhs)),
+            expected: Fp::from(expected as u64),
+        }
+    }
+
+    // the u8 table needs 256 rows
+    const K: u32 = 9;
+
+    #[test]
+    fn lt_3_lt_5() {
+        MockProver::run(K, &lt_circuit::<2>(3, 5, true), vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn lt_5_not_lt_5() {
+        MockProver::run(K, &lt_circuit::<2>(5, 5, false), vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn lt_5_not_lt_3() {
+        MockProver::run(K, &lt_circuit::<2>(5, 3, false), vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn lt_diff_overflow_fails() {
+        // 300 - 5 doesn't fit in a single byte
+        let prover = MockProver::run(K, &lt_circuit::<1>(300, 5, false), vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use halo2_gadgets::poseidon::{
+    primitives::{P128Pow5T3, Spec},
+    Pow5Config,
+};
+use halo2_proofs::{circuit::*, pasta::group::ff::PrimeField, plonk::*, poly::Rotation};
+
+use crate::{configure_poseidon, poseidon_hash_two, ACell};
+
+/// config of the merkle path circuit, the swap gate shares the poseidon state columns
+#[derive(Clone, Debug)]
+pub struct MerkleConfig<F: PrimeField> {
+    pub advice: [Column<Advice>; 3],
+    pub swap_selector: Selector,
+    pub instance: Column<Instance>,
+    pub poseidon: Pow5Config<F, 3, 2>,
+}
+
+/// proves `leaf` is in the tree with the public root, hashing one level per sibling
+#[derive(Clone, Debug)]
+pub struct MerkleProofCircuit<F> {
+    pub leaf: Value<F>,
+    /// siblings from the leaf up, the path depth is `siblings.len()`
+    pub siblings: Vec<Value<F>>,
+    /// `true` when the current node is the right child at that level, one per sibling
+    pub directions: Vec<Value<bool>>,
+}
+
+impl<F: PrimeField> MerkleProofCircuit<F>
+where
+    P128Pow5T3: Spec<F, 3, 2>,
+{
+    // order (node, sibling) by the direction bit, returning (left, right)
+    fn swap(
+        &self,
+        config: &MerkleConfig<F>,
+        mut layouter: impl Layouter<F>,
+        node: &ACell<F>,
+        sibling: Value<F>,
+        direction: Value<bool>,
+    ) -> Result<(ACell<F>, ACell<F>), Error> {
+        layouter.assign_region(
+            || "swap",
+            |mut region| {
+                config.swap_selector.enable(&mut region, 0)?;
+
+                let [col_a, col_b, col_bit] = config.advice;
+                node.0.copy_advice(|| "node", &mut region, col_a, 0)?;
+                region.assign_advice(|| "sibling", col_b, 0, || sibling)?;
+                region.assign_advice(
+                    || "direction",
+                    col_bit,
+                    0,
+                    || direction.map(|d| F::from(d as u64)),
+                )?;
+
+                let node = node.value().copied();
+                let (left, right) = node
+                    .zip(sibling)
+                    .zip(direction)
+                    .map(|((node, sibling), d)| if d { (sibling, node) } else { (node, sibling) })
+                    .unzip();
+
+                let left = region.assign_advice(|| "left", col_a, 1, || left)?;
+                let right = region.assign_advice(|| "right", col_b, 1, || right)?;
+                Ok((ACell(left), ACell(right)))
+            },
+        )
+    }
+}
+
+impl<F: PrimeField> Circuit<F> for MerkleProofCircuit<F>
+where
+    P128Pow5T3: Spec<F, 3, 2>,
+{
+    type Config = MerkleConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            leaf: Value::unknown(),
+            siblings: vec![Value::unknown(); self.siblings.len()],
+            directions: vec![Value::unknown(); self.directions.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let swap_selector = meta.selector();
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+
+        // poseidon enables equality on its state columns
+        let poseidon = configure_poseidon(meta, advice);
+
+        meta.create_gate("conditional swap", |meta| {
+            // | col_a | col_b   | col_bit | selector |
+            // | node  | sibling |    d    |     s    |
+            // | left  | right   |         |          |
+            let s = meta.query_selector(swap_selector);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let d = meta.query_advice(advice[2], Rotation::cur());
+            let left = meta.query_advice(advice[0], Rotation::next());
+            let right = meta.query_advice(advice[1], Rotation::next());
+
+            let one = Expression::Constant(F::ONE);
+            vec![
+                // d is a boolean
+                s.clone() * d.clone() * (one - d.clone()),
+                // left = a if d = 0, b if d = 1
+                s.clone() * (left - a.clone() - d.clone() * (b.clone() - a.clone())),
+                // right = b if d = 0, a if d = 1
+                s * (right - b.clone() - d * (a - b)),
+            ]
+        });
+
+        MerkleConfig {
+            advice,
+            swap_selector,
+            instance,
+            poseidon,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // one direction bit per sibling
+        if self.siblings.len() != self.directions.len() {
+            return Err(Error::Synthesis);
+        }
+
+        let mut node = layouter.assign_region(
+            || "leaf",
+            |mut region| {
+                region
+                    .assign_advice(|| "leaf", config.advice[0], 0, || self.leaf)
+                    .map(ACell)
+            },
+        )?;
+
+        for (sibling, direction) in self.siblings.iter().zip(&self.directions) {
+            let (left, right) = self.swap(
+                &config,
+                layouter.namespace(|| "swap"),
+                &node,
+                *sibling,
+                *direction,
+            )?;
+            node = poseidon_hash_two(
+                &config.poseidon,
+                layouter.namespace(|| "level"),
+                left,
+                right,
+            )?;
+        }
+
+        layouter.constrain_instance(node.cell(), config.instance, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_gadgets::poseidon::primitives::{self, ConstantLength};
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    fn hash(left: Fp, right: Fp) -> Fp {
+        primitives::Hash::<_, P128Pow5T3, ConstantLength<2>, 3, 2>::init().hash([left, right])
+    }
+
+    fn depth_3() -> (MerkleProofCircuit<Fp>, Fp) {
+        let leaf = Fp::from(7);
+        let siblings = [Fp::from(1), Fp::from(2), Fp::from(3)];
+        let directions = [false, true, false];
+
+        let root = siblings
+            .iter()
+            .zip(directions)
+            .fold(leaf, |node, (sibling, d)| {
+                if d {
+                    hash(*sibling, node)
+                } else {
+                    hash(node, *sibling)
+                }
+            });
+
+        let circuit = MerkleProofCircuit {
+            leaf: Value::known(leaf),
+            siblings: siblings.map(Value::known).to_vec(),
+            directions: directions.map(Value::known).to_vec(),
+        };
+        (circuit, root)
+    }
+
+    #[test]
+    fn merkle_depth_3() {
+        let (circuit, root) = depth_3();
+        MockProver::run(8, &circuit, vec![vec![root]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn merkle_wrong_sibling_fails() {
+        let (mut circuit, root) = depth_3();
+        circuit.siblings[1] = Value::known(Fp::from(4));
+        let prover = MockProver::run(8, &circuit, vec![vec![root]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn merkle_missing_direction_is_rejected() {
+        let (mut circuit, root) = depth_3();
+        circuit.directions.pop();
+        assert!(matches!(
+            MockProver::run(8, &circuit, vec![vec![root]]),
+            Err(Error::Synthesis)
+        ));
+    }
+}
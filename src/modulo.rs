@@ -0,0 +1,213 @@
+use halo2_proofs::{circuit::*, pasta::group::ff::PrimeField, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::{
+    ACell, FiboChip, FiboConfig, RangeCheckChip, RangeConfig, RangeTableChip, RangeTableConfig,
+};
+
+/// config of `x = q * M + r`: the row of (x, q, r) and the range checks of q and r
+#[derive(Clone, Debug)]
+pub struct ModConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+    /// `r < M`
+    pub remainder: RangeConfig,
+    /// `q < 2^Q_BITS`
+    pub quotient: RangeTableConfig,
+}
+
+/// `x mod M` of a cell, as the remainder cell
+/// note: without a bound on q, any r would do, as `(x - r) / M` always exists in the field.
+/// `q < 2^Q_BITS` keeps `q * M + r` below the modulus, so only `x < M * 2^Q_BITS` can be reduced
+pub struct ModChip<F: PrimeField, const M: usize, const Q_BITS: usize> {
+    config: ModConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: PrimeField, const M: usize, const Q_BITS: usize> ModChip<F, M, Q_BITS> {
+    pub fn construct(config: ModConfig) -> Self {
+        ModChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> ModConfig {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let selector = meta.selector();
+        let remainder = RangeCheckChip::<F, M>::configure(meta);
+        let quotient = RangeTableChip::<F, Q_BITS>::configure(meta);
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.create_gate("mod", |meta| {
+            // | col_x | col_q | col_r | selector |
+            // |   x   |   q   |   r   |     s    |
+            let s = meta.query_selector(selector);
+            let [x, q, r] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            vec![s * (x - q * Expression::Constant(F::from(M as u64)) - r)]
+        });
+
+        ModConfig {
+            advice,
+            selector,
+            remainder,
+            quotient,
+        }
+    }
+
+    /// fill the table of the quotient range check, once per circuit
+    pub fn load_table(&self, layouter: impl Layouter<F>) -> Result<(), Error> {
+        RangeTableChip::<F, Q_BITS>::construct(self.config.quotient.clone()).load_table(layouter)
+    }
+
+    /// copy `x` in and split it into `q * M + r`, returning the r cell
+    pub fn reduce(&self, mut layouter: impl Layouter<F>, x: &ACell<F>) -> Result<ACell<F>, Error> {
+        let (q, r) = x.value().map(|x| split(x, M as u64)).unzip();
+        let q = RangeTableChip::<F, Q_BITS>::construct(self.config.quotient.clone())
+            .assign(layouter.namespace(|| "q range"), q)?;
+        let r = RangeCheckChip::<F, M>::construct(self.config.remainder.clone())
+            .assign(layouter.namespace(|| "r range"), r)?;
+
+        layouter.assign_region(
+            || "mod",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                // copy constraint
+                let [col_x, col_q, col_r] = self.config.advice;
+                x.0.copy_advice(|| "x", &mut region, col_x, 0)?;
+                q.0.copy_advice(|| "q", &mut region, col_q, 0)?;
+                r.0.copy_advice(|| "r", &mut region, col_r, 0).map(ACell)
+            },
+        )
+    }
+}
+
+// (x / m, x % m) of the integer in the little endian repr
+fn split<F: PrimeField>(x: &F, m: u64) -> (F, F) {
+    let mut bytes: Vec<u8> = x.to_repr().as_ref().to_vec();
+    let mut rem = 0u128;
+    for byte in bytes.iter_mut().rev() {
+        let cur = (rem << 8) | *byte as u128;
+        *byte = (cur / m as u128) as u8;
+        rem = cur % m as u128;
+    }
+    let mut repr = F::Repr::default();
+    repr.as_mut().copy_from_slice(&bytes);
+    (F::from_repr(repr).unwrap(), F::from(rem as u64))
+}
+
+/// proves `fib(n) mod M`, where the sequence starts from (a, b)
+/// the remainder is public at `instance[0]`, the term and the quotient stay private
+#[derive(Clone, Debug, Default)]
+pub struct FiboModCircuit<F: PrimeField, const M: usize, const Q_BITS: usize> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub n: usize,
+}
+
+impl<F: PrimeField, const M: usize, const Q_BITS: usize> Circuit<F>
+    for FiboModCircuit<F, M, Q_BITS>
+{
+    type Config = (FiboConfig, ModConfig);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            n: self.n,
+            ..Default::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        (
+            FiboChip::configure_standalone(meta),
+            ModChip::<F, M, Q_BITS>::configure(meta),
+        )
+    }
+
+    fn synthesize(
+        &self,
+        (fibo, modulo): Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // same as `FiboCircuit`, n = 1 would only reduce the seed's a
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let fibo_chip = FiboChip::construct(fibo);
+        let mod_chip = ModChip::<F, M, Q_BITS>::construct(modulo);
+        mod_chip.load_table(layouter.namespace(|| "table"))?;
+
+        let cells = fibo_chip.assign_sequence_single_region(
+            layouter.namespace(|| "sequence"),
+            self.a,
+            self.b,
+            self.n,
+        )?;
+        let r = mod_chip.reduce(layouter.namespace(|| "mod"), cells.last().unwrap())?;
+        fibo_chip.expose_public(layouter.namespace(|| "out"), &r, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{
+        dev::{MockProver, VerifyFailure},
+        pasta::Fp,
+    };
+
+    // fib(10) = 55 = 5 * 10 + 5
+    fn fibo_10th_mod_10() -> FiboModCircuit<Fp, 10, 4> {
+        FiboModCircuit {
+            a: Some(Fp::one()),
+            b: Some(Fp::one()),
+            n: 10,
+        }
+    }
+
+    #[test]
+    fn fibo_mod_10th_mod_10() {
+        // 2^4 table rows + 6 reserved
+        MockProver::run(5, &fibo_10th_mod_10(), vec![vec![Fp::from(5)]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_mod_wrong_remainder_fails() {
+        let prover = MockProver::run(5, &fibo_10th_mod_10(), vec![vec![Fp::from(4)]]).unwrap();
+        let failures = prover.verify().unwrap_err();
+        assert!(failures
+            .iter()
+            .all(|f| matches!(f, VerifyFailure::Permutation { .. })));
+    }
+
+    #[test]
+    fn fibo_mod_split() {
+        assert_eq!(split(&Fp::from(55), 10), (Fp::from(5), Fp::from(5)));
+        // above a byte, and above u64
+        assert_eq!(split(&Fp::from(1000), 7), (Fp::from(142), Fp::from(6)));
+        let big = Fp::from_u128(u128::MAX);
+        assert_eq!(split(&big, 3), (Fp::from_u128(u128::MAX / 3), Fp::zero()));
+    }
+
+    #[test]
+    fn fibo_mod_too_short_is_rejected() {
+        for n in [0, 1] {
+            let circuit = FiboModCircuit {
+                n,
+                ..fibo_10th_mod_10()
+            };
+            assert!(matches!(
+                MockProver::run(5, &circuit, vec![vec![Fp::one()]]),
+                Err(Error::Synthesis)
+            ));
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use halo2_proofs::{circuit::*, pasta::group::ff::PrimeField, plonk::*};
+
+use crate::{ACell, FiboChip, FiboConfig, LtChip, LtConfig};
+
+/// constrain every term to be at least the previous one, `!(terms[i + 1] < terms[i])`
+/// note: the `lt` outputs are pinned to 0 through the constants column, so the circuit needs
+/// one (e.g. the one of `FiboConfig`). two terms more than 2^(8 * N_BYTES) apart fail either way
+pub fn constrain_non_decreasing<F: PrimeField, const N_BYTES: usize>(
+    mut layouter: impl Layouter<F>,
+    lt_chip: &LtChip<F, N_BYTES>,
+    terms: &[ACell<F>],
+) -> Result<(), Error> {
+    for (i, pair) in terms.windows(2).enumerate() {
+        let lt =
+            lt_chip.assign_cells(layouter.namespace(|| format!("lt {i}")), &pair[1], &pair[0])?;
+        layouter.assign_region(
+            || format!("not less {i}"),
+            |mut region| region.constrain_constant(lt.cell(), F::ZERO),
+        )?;
+    }
+    Ok(())
+}
+
+/// proves the sequence up to the `n`-th term from (a, b) is non-decreasing, and exposes the
+/// `n`-th term at `instance[0]` like `FiboCircuit`
+/// note: the add gate alone doesn't imply it, a seed like (5, -1) gives 5, -1, 4, 3, ...
+/// in the field. the u8 table of `LtChip` needs `k >= 9`
+#[derive(Clone, Debug, Default)]
+pub struct MonotonicFiboCircuit<F, const N_BYTES: usize> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub n: usize,
+}
+
+impl<F: PrimeField, const N_BYTES: usize> Circuit<F> for MonotonicFiboCircuit<F, N_BYTES> {
+    type Config = (FiboConfig, LtConfig<N_BYTES>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            n: self.n,
+            ..Default::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        (
+            FiboChip::configure_standalone(meta),
+            LtChip::configure(meta),
+        )
+    }
+
+    fn synthesize(
+        &self,
+        (fibo, lt): Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // same as `FiboCircuit`, n = 0 leaves no term to expose
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let fibo_chip = FiboChip::construct(fibo);
+        let lt_chip = LtChip::construct(lt);
+        lt_chip.load_table(layouter.namespace(|| "table"))?;
+
+        let terms = fibo_chip.assign_full_sequence(
+            layouter.namespace(|| "sequence"),
+            self.a,
+            self.b,
+            self.n,
+        )?;
+        constrain_non_decreasing(layouter.namespace(|| "monotonic"), &lt_chip, &terms)?;
+        fibo_chip.expose_public(layouter.namespace(|| "out"), terms.last().unwrap(), 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nth_fibonacci, FiboSubChip, FiboSubConfig};
+    use halo2_proofs::{
+        dev::{MockProver, VerifyFailure},
+        pasta::Fp,
+    };
+
+    // the u8 table needs 256 rows
+    const K: u32 = 9;
+
+    #[test]
+    fn monotonic_from_1_1() {
+        let circuit = MonotonicFiboCircuit::<Fp, 2> {
+            a: Some(Fp::one()),
+            b: Some(Fp::one()),
+            n: 20,
+        };
+        let out = nth_fibonacci(20, Fp::one(), Fp::one());
+        MockProver::run(K, &circuit, vec![vec![out]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn monotonic_too_short_is_rejected() {
+        for n in [0, 1] {
+            let circuit = MonotonicFiboCircuit::<Fp, 2> {
+                a: Some(Fp::one()),
+                b: Some(Fp::one()),
+                n,
+            };
+            assert!(matches!(
+                MockProver::run(K, &circuit, vec![vec![Fp::one()]]),
+                Err(Error::Synthesis)
+            ));
+        }
+    }
+
+    // terms of the subtraction chip walking down from (fib(12), fib(11)), which is a valid
+    // sequence of its own gate but decreasing
+    struct DecreasingCircuit;
+
+    impl Circuit<Fp> for DecreasingCircuit {
+        type Config = (FiboSubConfig, LtConfig<2>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            // `constrain_non_decreasing` needs a constants column, the sub chip has none
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
+            (FiboSubChip::configure(meta), LtChip::configure(meta))
+        }
+
+        fn synthesize(
+            &self,
+            (sub, lt): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let fib = |n| nth_fibonacci(n, Fp::one(), Fp::one());
+            let sub_chip = FiboSubChip::construct(sub);
+            let lt_chip = LtChip::construct(lt);
+            lt_chip.load_table(layouter.namespace(|| "table"))?;
+
+            let (a, mut prev_b, mut prev_c) = sub_chip.assign_first_row(
+                layouter.namespace(|| "first row"),
+                Some(fib(12)),
+                Some(fib(11)),
+            )?;
+            let mut terms = vec![a, prev_b.clone(), prev_c.clone()];
+            for _ in 0..3 {
+                let c = sub_chip.assign_row(layouter.namespace(|| "next row"), &prev_b, &prev_c)?;
+                terms.push(c.clone());
+                (prev_b, prev_c) = (prev_c, c);
+            }
+            constrain_non_decreasing(layouter.namespace(|| "monotonic"), &lt_chip, &terms)
+        }
+    }
+
+    #[test]
+    fn monotonic_decreasing_fails() {
+        let failures = MockProver::run(K, &DecreasingCircuit, vec![vec![]])
+            .unwrap()
+            .verify()
+            .unwrap_err();
+        // every `lt` is 1, not the pinned 0
+        assert!(!failures.is_empty());
+        assert!(failures
+            .iter()
+            .all(|f| matches!(f, VerifyFailure::Permutation { .. })));
+    }
+}
@@ -0,0 +1,180 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// same layout as `FiboConfig`, but the gate multiplies
+#[derive(Clone, Debug)]
+pub struct MulConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+}
+
+pub struct MulChip<F: Field> {
+    config: MulConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> MulChip<F> {
+    pub fn construct(config: MulConfig) -> Self {
+        MulChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /// the advice columns can be shared with another chip, see `FiboMulCircuit`
+    pub fn configure(meta: &mut ConstraintSystem<F>, advice: [Column<Advice>; 3]) -> MulConfig {
+        let [col_a, col_b, col_c] = advice;
+        let selector = meta.selector();
+
+        meta.enable_equality(col_a);
+        meta.enable_equality(col_b);
+        meta.enable_equality(col_c);
+
+        meta.create_gate("mul", |meta| {
+            // | col_a | col_b | col_c | selector |
+            // |   a   |   b   |   c   |     s    |
+            let s = meta.query_selector(selector);
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let c = meta.query_advice(col_c, Rotation::cur());
+
+            // a * b = c, degree 3 with the selector (the add gate is degree 2)
+            vec![s * (a * b - c)]
+        });
+
+        MulConfig {
+            advice: [col_a, col_b, col_c],
+            selector,
+        }
+    }
+
+    /// `configure` on freshly allocated advice columns
+    pub fn configure_standalone(meta: &mut ConstraintSystem<F>) -> MulConfig {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        Self::configure(meta, advice)
+    }
+
+    /// assign a, b and c = a * b in one row, returning the c cell
+    pub fn assign_mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "mul",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                region.assign_advice(|| "a", self.config.advice[0], 0, || a)?;
+                region.assign_advice(|| "b", self.config.advice[1], 0, || b)?;
+                region
+                    .assign_advice(|| "c", self.config.advice[2], 0, || a * b)
+                    .map(ACell)
+            },
+        )
+    }
+
+    /// same as `assign_mul`, but a is copied from a cell assigned elsewhere
+    pub fn assign_mul_cell(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &ACell<F>,
+        b: Value<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "mul",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                // copy constraint
+                let a =
+                    a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                region.assign_advice(|| "b", self.config.advice[1], 0, || b)?;
+                let c = a.value().copied() * b;
+                region
+                    .assign_advice(|| "c", self.config.advice[2], 0, || c)
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    struct MulCircuit {
+        a: Value<Fp>,
+        b: Value<Fp>,
+        // assign this as c instead of a * b, to check the gate rejects it
+        bad_c: Option<Fp>,
+    }
+
+    impl Circuit<Fp> for MulCircuit {
+        type Config = MulConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                a: Value::unknown(),
+                b: Value::unknown(),
+                bad_c: None,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            MulChip::configure_standalone(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let Some(bad_c) = self.bad_c else {
+                let chip = MulChip::construct(config);
+                let c = chip.assign_mul(layouter.namespace(|| "mul"), self.a, self.b)?;
+                c.value().assert_if_known(|c| **c == Fp::from(42));
+                return Ok(());
+            };
+
+            layouter.assign_region(
+                || "bad mul",
+                |mut region| {
+                    config.selector.enable(&mut region, 0)?;
+                    region.assign_advice(|| "a", config.advice[0], 0, || self.a)?;
+                    region.assign_advice(|| "b", config.advice[1], 0, || self.b)?;
+                    region.assign_advice(|| "c", config.advice[2], 0, || Value::known(bad_c))?;
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn mul_6_times_7() {
+        let circuit = MulCircuit {
+            a: Value::known(Fp::from(6)),
+            b: Value::known(Fp::from(7)),
+            bad_c: None,
+        };
+        MockProver::run(4, &circuit, vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn mul_wrong_product_fails() {
+        let circuit = MulCircuit {
+            a: Value::known(Fp::from(6)),
+            b: Value::known(Fp::from(7)),
+            bad_c: Some(Fp::from(41)),
+        };
+        let prover = MockProver::run(4, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use halo2_proofs::pasta::group::ff::PrimeField;
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+
+use crate::{
+    column_index, parse_field, selector_index, to_decimal, FiboCircuit, FiboConfig, FiboError,
+};
+
+/// scalar inputs of `FiboCircuit`, field elements as decimal strings
+/// note: `from_params` takes `0x` hex as well, see `parse_field`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FiboCircuitParams {
+    pub a: String,
+    pub b: String,
+    pub n: usize,
+}
+
+impl<F: PrimeField> FiboCircuit<F> {
+    /// same checks as `FiboCircuitBuilder::build`
+    pub fn from_params(params: &FiboCircuitParams) -> Result<Self, FiboError> {
+        FiboCircuit::builder()
+            .seed(parse_field(&params.a)?, parse_field(&params.b)?)
+            .length(params.n)
+            .build()
+    }
+
+    /// `None` without witnesses, there is no seed to write down
+    pub fn to_params(&self) -> Option<FiboCircuitParams> {
+        Some(FiboCircuitParams {
+            a: to_decimal(&self.a?),
+            b: to_decimal(&self.b?),
+            n: self.n,
+        })
+    }
+}
+
+/// columns serialize as their index, e.g. `{"advice":[0,1,2],"selector":0,...}`
+/// note: serialize only, halo2 has no public constructor for columns, `configure` rebuilds them
+impl Serialize for FiboConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut config = serializer.serialize_struct("FiboConfig", 5)?;
+        config.serialize_field("advice", &self.advice.map(|c| column_index(&c)))?;
+        config.serialize_field("selector", &selector_index(&self.selector))?;
+        config.serialize_field("next_selector", &selector_index(&self.next_selector))?;
+        config.serialize_field("constants", &column_index(&self.constants))?;
+        config.serialize_field("instance", &column_index(&self.instance))?;
+        config.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nth_fibonacci, FiboChip};
+    use halo2_proofs::{dev::MockProver, pasta::Fp, plonk::ConstraintSystem};
+
+    #[test]
+    fn fibo_params_json_roundtrip() {
+        let params = FiboCircuitParams {
+            a: "2".to_string(),
+            b: "3".to_string(),
+            n: 10,
+        };
+        let json = serde_json::to_string(&params).unwrap();
+        assert_eq!(json, r#"{"a":"2","b":"3","n":10}"#);
+
+        let loaded: FiboCircuitParams = serde_json::from_str(&json).unwrap();
+        let circuit = FiboCircuit::<Fp>::from_params(&loaded).unwrap();
+        assert_eq!(circuit.to_params(), Some(params));
+
+        let expected = nth_fibonacci(10, Fp::from(2), Fp::from(3));
+        MockProver::run(4, &circuit, vec![vec![expected]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_circuit_params_roundtrip() {
+        let circuit = FiboCircuit::builder()
+            .seed(Fp::from(2), Fp::from(3))
+            .length(10)
+            .build()
+            .unwrap();
+        let params = circuit.to_params().unwrap();
+        assert_eq!(FiboCircuit::from_params(&params).unwrap(), circuit);
+
+        let other = FiboCircuitParams { n: 11, ..params };
+        assert_ne!(FiboCircuit::from_params(&other).unwrap(), circuit);
+    }
+
+    #[test]
+    fn fibo_config_serializes_indices() {
+        let mut meta = ConstraintSystem::<Fp>::default();
+        let config = FiboChip::configure_standalone(&mut meta);
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"advice":[0,1,2],"selector":0,"next_selector":1,"constants":0,"instance":0}"#
+        );
+    }
+}
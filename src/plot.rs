@@ -0,0 +1,48 @@
+use halo2_proofs::{dev::CircuitLayout, pasta::Fp};
+use plotters::prelude::*;
+use std::path::Path;
+
+use crate::FiboCircuit;
+
+/// render the layout of the fibonacci circuit computing the `n`-th term into a PNG
+/// columns are rendered in configure order: instance, advice a b c, constants, then the two selectors
+/// note: requires the `dev-graph` feature, e.g. `cargo run --features dev-graph`
+pub fn plot_fibonacci(
+    filename: impl AsRef<Path>,
+    k: u32,
+    n: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let circuit = FiboCircuit::<Fp>::empty(n);
+
+    let root = BitMapBackend::new(filename.as_ref(), (500, 800)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let root = root.titled(&format!("Fibonacci n = {n}, k = {k}"), ("sans-serif", 20))?;
+
+    // header row with one label per column, above the layout itself
+    let (header, body) = root.split_vertically(30);
+    let labels = ["instance", "a", "b", "c", "constants", "add", "next row"];
+    for (area, label) in header.split_evenly((1, labels.len())).iter().zip(labels) {
+        area.draw_text(label, &("sans-serif", 15).into(), (5, 5))?;
+    }
+
+    CircuitLayout::default()
+        .show_labels(true)
+        .mark_equality_cells(true)
+        .show_equality_constraints(true)
+        .render(k, &circuit, &body)?;
+    root.present()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fibo_plot_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fibonacci-layout.png");
+        plot_fibonacci(&path, 4, 10).unwrap();
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+    }
+}
@@ -0,0 +1,277 @@
+use halo2_gadgets::poseidon::{
+    primitives::{self, ConstantLength, P128Pow5T3, Spec},
+    Hash, Pow5Chip, Pow5Config,
+};
+use halo2_proofs::{circuit::*, pasta::group::ff::PrimeField, plonk::*};
+
+use crate::{ACell, FiboChip, FiboConfig};
+
+/// fibonacci columns plus the poseidon chip, which reuses the 3 fibonacci advice columns as its state
+#[derive(Clone, Debug)]
+pub struct FiboHashConfig<F: PrimeField> {
+    pub fibo: FiboConfig,
+    pub poseidon: Pow5Config<F, 3, 2>,
+}
+
+/// hash two terms `poseidon(a, b)` and return the digest cell
+pub fn hash_two_fibonacci_terms<F: PrimeField>(
+    config: &FiboHashConfig<F>,
+    layouter: impl Layouter<F>,
+    a: ACell<F>,
+    b: ACell<F>,
+) -> Result<ACell<F>, Error>
+where
+    P128Pow5T3: Spec<F, 3, 2>,
+{
+    poseidon_hash_two(&config.poseidon, layouter, a, b)
+}
+
+/// `poseidon(a, b)` on any pair of cells, shared by the circuits using the poseidon chip
+pub fn poseidon_hash_two<F: PrimeField>(
+    config: &Pow5Config<F, 3, 2>,
+    mut layouter: impl Layouter<F>,
+    a: ACell<F>,
+    b: ACell<F>,
+) -> Result<ACell<F>, Error>
+where
+    P128Pow5T3: Spec<F, 3, 2>,
+{
+    let chip = Pow5Chip::construct(config.clone());
+    let hasher = Hash::<_, _, P128Pow5T3, ConstantLength<2>, 3, 2>::init(
+        chip,
+        layouter.namespace(|| "init"),
+    )?;
+    hasher
+        .hash(layouter.namespace(|| "hash"), [a.0, b.0])
+        .map(ACell)
+}
+
+/// configure the poseidon chip on top of existing `state` columns
+pub fn configure_poseidon<F: PrimeField>(
+    meta: &mut ConstraintSystem<F>,
+    state: [Column<Advice>; 3],
+) -> Pow5Config<F, 3, 2>
+where
+    P128Pow5T3: Spec<F, 3, 2>,
+{
+    let partial_sbox = meta.advice_column();
+    let rc_a = [(); 3].map(|_| meta.fixed_column());
+    let rc_b = [(); 3].map(|_| meta.fixed_column());
+    // poseidon loads its padding and initial state as constants
+    meta.enable_constant(rc_b[0]);
+
+    Pow5Chip::configure::<P128Pow5T3>(meta, state, partial_sbox, rc_a, rc_b)
+}
+
+/// computes the sequence up to the `n`-th term and exposes `poseidon(F(n-1), F(n))` at row 0
+#[derive(Debug)]
+pub struct FiboHashCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub n: usize,
+}
+
+impl<F: PrimeField> Circuit<F> for FiboHashCircuit<F>
+where
+    P128Pow5T3: Spec<F, 3, 2>,
+{
+    type Config = FiboHashConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: None,
+            b: None,
+            n: self.n,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let fibo = FiboChip::configure_standalone(meta);
+        let poseidon = configure_poseidon(meta, fibo.advice);
+        FiboHashConfig { fibo, poseidon }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // the hash needs the last two terms
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let fibo_chip = FiboChip::construct(config.fibo.clone());
+        let mut cells = fibo_chip.assign_full_sequence(
+            layouter.namespace(|| "sequence"),
+            self.a,
+            self.b,
+            self.n,
+        )?;
+
+        let last = cells.pop().unwrap();
+        let second_last = cells.pop().unwrap();
+        let digest = hash_two_fibonacci_terms(
+            &config,
+            layouter.namespace(|| "poseidon"),
+            second_last,
+            last,
+        )?;
+
+        fibo_chip.expose_public(layouter.namespace(|| "digest"), &digest, 0)?;
+        Ok(())
+    }
+}
+
+/// `poseidon(a, b)` outside of the circuit, the public commitment of `FiboCommittedSeedCircuit`
+pub fn seed_commitment<F: PrimeField>(a: F, b: F) -> F
+where
+    P128Pow5T3: Spec<F, 3, 2>,
+{
+    primitives::Hash::<_, P128Pow5T3, ConstantLength<2>, 3, 2>::init().hash([a, b])
+}
+
+/// the seed stays private, only its commitment `seed_commitment(a, b)` is exposed at row 0
+/// and the `n`-th term at row 1
+/// note: the hash is taken over the same cells the sequence starts from, so the proof shows
+/// the committed seed is the one the term was computed from
+#[derive(Debug)]
+pub struct FiboCommittedSeedCircuit<F> {
+    pub a: Option<F>,
+    pub b: Option<F>,
+    pub n: usize,
+}
+
+impl<F: PrimeField> Circuit<F> for FiboCommittedSeedCircuit<F>
+where
+    P128Pow5T3: Spec<F, 3, 2>,
+{
+    type Config = FiboHashConfig<F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            a: None,
+            b: None,
+            n: self.n,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        FiboHashCircuit::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        // the commitment is taken over the two seed cells
+        if self.n < 2 {
+            return Err(Error::Synthesis);
+        }
+
+        let fibo_chip = FiboChip::construct(config.fibo.clone());
+        let cells = fibo_chip.assign_full_sequence(
+            layouter.namespace(|| "sequence"),
+            self.a,
+            self.b,
+            self.n,
+        )?;
+
+        let commitment = poseidon_hash_two(
+            &config.poseidon,
+            layouter.namespace(|| "commit seed"),
+            cells[0].clone(),
+            cells[1].clone(),
+        )?;
+
+        fibo_chip.expose_public(layouter.namespace(|| "commitment"), &commitment, 0)?;
+        fibo_chip.expose_public(layouter.namespace(|| "out"), cells.last().unwrap(), 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_gadgets::poseidon::primitives;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    fn circuit() -> FiboHashCircuit<Fp> {
+        FiboHashCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+            n: 10,
+        }
+    }
+
+    #[test]
+    fn fibo_hash_9th_and_10th() {
+        // reference poseidon over the 9th and 10th terms
+        let digest = primitives::Hash::<_, P128Pow5T3, ConstantLength<2>, 3, 2>::init()
+            .hash([Fp::from(34), Fp::from(55)]);
+
+        MockProver::run(7, &circuit(), vec![vec![digest]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn fibo_hash_wrong_digest_fails() {
+        let digest = primitives::Hash::<_, P128Pow5T3, ConstantLength<2>, 3, 2>::init()
+            .hash([Fp::from(34), Fp::from(56)]);
+
+        let prover = MockProver::run(7, &circuit(), vec![vec![digest]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn fibo_committed_seed() {
+        let circuit = |a: u64, b: u64| FiboCommittedSeedCircuit {
+            a: Some(Fp::from(a)),
+            b: Some(Fp::from(b)),
+            n: 10,
+        };
+        let commitment = seed_commitment(Fp::from(2), Fp::from(3));
+        // 2, 3, 5, 8, 13, 21, 34, 55, 89, 144
+        let instance = |commitment, out: u64| vec![vec![commitment, Fp::from(out)]];
+        MockProver::run(7, &circuit(2, 3), instance(commitment, 144))
+            .unwrap()
+            .assert_satisfied();
+
+        // the 10th term of another seed is right, but the seed doesn't open the commitment
+        let prover = MockProver::run(7, &circuit(1, 1), instance(commitment, 55)).unwrap();
+        assert!(prover.verify().is_err());
+        // the committed seed, but a wrong term
+        let prover = MockProver::run(7, &circuit(2, 3), instance(commitment, 145)).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn fibo_committed_seed_too_short_is_rejected() {
+        for n in [0, 1] {
+            let circuit = FiboCommittedSeedCircuit {
+                a: Some(Fp::one()),
+                b: Some(Fp::one()),
+                n,
+            };
+            let commitment = seed_commitment(Fp::one(), Fp::one());
+            assert!(matches!(
+                MockProver::run(7, &circuit, vec![vec![commitment, Fp::one()]]),
+                Err(Error::Synthesis)
+            ));
+        }
+    }
+
+    #[test]
+    fn fibo_hash_too_short_is_rejected() {
+        for n in [0, 1] {
+            let circuit = FiboHashCircuit { n, ..circuit() };
+            assert!(matches!(
+                MockProver::run(7, &circuit, vec![vec![Fp::one()]]),
+                Err(Error::Synthesis)
+            ));
+        }
+    }
+}
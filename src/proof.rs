@@ -0,0 +1,1118 @@
+use halo2_proofs::{
+    arithmetic::CurveAffine,
+    pasta::{
+        group::ff::{FromUniformBytes, PrimeField},
+        EpAffine, EqAffine, Fp, Fq,
+    },
+    plonk::*,
+    poly::commitment::Params,
+    transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use rand_core::{OsRng, RngCore};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{check_capacity, nth_fibonacci, FiboCircuit, FiboError};
+
+/// commitment params for circuit size `k`
+/// note: IPA params are deterministic (no trusted setup), but generation is slow for large k
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "params", fields(elapsed_ms = tracing::field::Empty))
+)]
+pub fn params_for(k: u32) -> Params<EqAffine> {
+    #[cfg(feature = "tracing")]
+    let _elapsed = Elapsed::start();
+    Params::new(k)
+}
+
+// records `elapsed_ms` on the current span when dropped, at the end of the instrumented body
+#[cfg(feature = "tracing")]
+struct Elapsed(std::time::Instant);
+
+#[cfg(feature = "tracing")]
+impl Elapsed {
+    fn start() -> Self {
+        Elapsed(std::time::Instant::now())
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for Elapsed {
+    fn drop(&mut self) {
+        let elapsed_ms = self.0.elapsed().as_millis() as u64;
+        tracing::Span::current().record("elapsed_ms", elapsed_ms);
+    }
+}
+
+/// read cached params from `path`, or generate them and write the cache if it doesn't exist
+pub fn load_or_generate_params(
+    k: u32,
+    path: impl AsRef<Path>,
+) -> Result<Params<EqAffine>, FiboError> {
+    let path = path.as_ref();
+    if path.exists() {
+        let bytes = fs::read(path)?;
+        // params file starts with k, reject a cache made for another circuit size
+        let cached_k = bytes
+            .get(..4)
+            .map(|k| u32::from_le_bytes(k.try_into().unwrap()));
+        if cached_k != Some(k) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "cached params at {} are for k = {cached_k:?}, expected k = {k}",
+                    path.display()
+                ),
+            )
+            .into());
+        }
+        return Ok(Params::read(&mut &bytes[..])?);
+    }
+
+    let params = params_for(k);
+    let mut bytes = vec![];
+    params.write(&mut bytes)?;
+    // written aside and renamed, so a concurrent reader never sees a partial file
+    static WRITES: AtomicUsize = AtomicUsize::new(0);
+    let unique = WRITES.fetch_add(1, Ordering::Relaxed);
+    let tmp = path.with_extension(format!("tmp-{}-{unique}", std::process::id()));
+    fs::write(&tmp, bytes)?;
+    fs::rename(tmp, path)?;
+    Ok(params)
+}
+
+/// cache file of the params of size `k` used by `prove_and_verify`, in the system temp dir,
+/// e.g. `/tmp/fibo-params/params-k4.bin`. nothing removes them, delete the dir to clear it
+pub fn params_cache_path(k: u32) -> PathBuf {
+    std::env::temp_dir()
+        .join("fibo-params")
+        .join(format!("params-k{k}.bin"))
+}
+
+/// generate the keys of any circuit and prove it, `public_inputs[i]` holds the rows of
+/// instance column `i` as in `verify_fibonacci`
+/// returns proof bytes from the blake2b transcript
+pub fn prove<C: Circuit<Fp>>(
+    k: u32,
+    circuit: C,
+    public_inputs: &[&[Fp]],
+) -> Result<Vec<u8>, FiboError> {
+    prove_circuit_with_rng(&params_for(k), circuit, public_inputs, OsRng, vec![])
+}
+
+/// verify a `prove` proof, the vk is rebuilt from `circuit`, whose witness is ignored
+pub fn verify<C: Circuit<Fp>>(
+    k: u32,
+    circuit: &C,
+    public_inputs: &[&[Fp]],
+    proof: &[u8],
+) -> Result<(), FiboError> {
+    let params = params_for(k);
+    let vk = keygen_vk(&params, &circuit.without_witnesses())?;
+    verify_with_params(&params, &vk, public_inputs, proof)
+}
+
+/// `prove` of the fibonacci circuit, checking its witness and capacity first
+/// returns proof bytes from the blake2b transcript
+/// note: with the `tracing` feature, this is the "prove" span, keygen included
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "prove",
+        skip(circuit, public_inputs),
+        fields(n = circuit.n, elapsed_ms = tracing::field::Empty)
+    )
+)]
+pub fn prove_fibonacci(
+    k: u32,
+    circuit: FiboCircuit<Fp>,
+    public_inputs: &[Fp],
+) -> Result<Vec<u8>, FiboError> {
+    #[cfg(feature = "tracing")]
+    let _elapsed = Elapsed::start();
+    // setup the commitment params (IPA over pasta needs no trusted setup)
+    prove_fibonacci_with_params(&params_for(k), circuit, public_inputs)
+}
+
+/// same as `prove_fibonacci`, but reuses already generated params
+pub fn prove_fibonacci_with_params(
+    params: &Params<EqAffine>,
+    circuit: FiboCircuit<Fp>,
+    public_inputs: &[Fp],
+) -> Result<Vec<u8>, FiboError> {
+    prove_with_rng(params, circuit, public_inputs, OsRng, vec![])
+}
+
+/// `prove_fibonacci`, writing the proof into `writer` (e.g. a file or socket) as the transcript
+/// goes instead of buffering it, returns the writer back
+/// note: the writer is not flushed, wrap a file in a `BufWriter` and flush it afterwards
+pub fn prove_fibonacci_to_writer<W: Write>(
+    k: u32,
+    circuit: FiboCircuit<Fp>,
+    public_inputs: &[Fp],
+    writer: W,
+) -> Result<W, FiboError> {
+    prove_with_rng(&params_for(k), circuit, public_inputs, OsRng, writer)
+}
+
+/// `prove_fibonacci` with the blinding randomness drawn from `ChaCha20Rng` seeded by `rng_seed`,
+/// so the same inputs and seed always give the same proof bytes, e.g. for test vectors
+/// note: the blinding is what hides the witness, never reuse a known seed for a private witness
+pub fn prove_fibonacci_deterministic(
+    k: u32,
+    circuit: FiboCircuit<Fp>,
+    public_inputs: &[Fp],
+    rng_seed: u64,
+) -> Result<Vec<u8>, FiboError> {
+    let rng = ChaCha20Rng::seed_from_u64(rng_seed);
+    prove_with_rng(&params_for(k), circuit, public_inputs, rng, vec![])
+}
+
+/// `prove_fibonacci_with_params` on a rayon pool of `num_threads` threads
+/// note: keygen and proving are dominated by FFTs and MSMs, which halo2 splits over the pool,
+/// so expect close to linear speedup up to the number of cores for large k (little for k < 10).
+/// the proof only depends on `rng`, so a seeded rng gives the same bytes for any thread count
+#[cfg(feature = "parallel")]
+pub fn prove_fibonacci_parallel(
+    params: &Params<EqAffine>,
+    circuit: FiboCircuit<Fp>,
+    public_inputs: &[Fp],
+    num_threads: usize,
+    rng: impl RngCore + Send,
+) -> Result<Vec<u8>, FiboError> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .map_err(io::Error::other)?;
+
+    // note: the `output` side channel is an `Rc`, which can't move into the pool, so it's dropped
+    let FiboCircuit {
+        a, b, n, planner, ..
+    } = circuit;
+    pool.install(|| {
+        let circuit = FiboCircuit {
+            a,
+            b,
+            n,
+            output: None,
+            planner,
+        };
+        prove_with_rng(params, circuit, public_inputs, rng, vec![])
+    })
+}
+
+// note: generic over the commitment curve, for both halves of the pasta cycle
+// the transcript is written into `writer`, which is handed back
+fn prove_with_rng<C: CurveAffine, W: Write>(
+    params: &Params<C>,
+    circuit: FiboCircuit<C::Scalar>,
+    public_inputs: &[C::Scalar],
+    rng: impl RngCore,
+    writer: W,
+) -> Result<W, FiboError>
+where
+    C::Scalar: FromUniformBytes<64>,
+{
+    // keygen would pass without a witness, but create_proof can't
+    if circuit.a.is_none() || circuit.b.is_none() {
+        return Err(FiboError::MissingWitness);
+    }
+    check_capacity(params.k(), circuit.n)?;
+
+    prove_circuit_with_rng(params, circuit, &[public_inputs], rng, writer)
+}
+
+// `prove_with_rng` of any circuit, without the fibonacci specific checks
+fn prove_circuit_with_rng<C: CurveAffine, Ci: Circuit<C::Scalar>, W: Write>(
+    params: &Params<C>,
+    circuit: Ci,
+    public_inputs: &[&[C::Scalar]],
+    rng: impl RngCore,
+    writer: W,
+) -> Result<W, FiboError>
+where
+    C::Scalar: FromUniformBytes<64>,
+{
+    // 1. keygen only depends on the circuit shape, not the witness
+    let vk = keygen_vk(params, &circuit.without_witnesses())?;
+    let pk = keygen_pk(params, vk, &circuit.without_witnesses())?;
+
+    prove_with_pk(params, &pk, circuit, public_inputs, rng, writer)
+}
+
+fn prove_with_pk<C: CurveAffine, Ci: Circuit<C::Scalar>, W: Write>(
+    params: &Params<C>,
+    pk: &ProvingKey<C>,
+    circuit: Ci,
+    public_inputs: &[&[C::Scalar]],
+    rng: impl RngCore,
+    writer: W,
+) -> Result<W, FiboError>
+where
+    C::Scalar: FromUniformBytes<64>,
+{
+    // 2. create proof with the witness, `public_inputs[i]` in instance column i
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(writer);
+    create_proof(
+        params,
+        pk,
+        &[circuit],
+        &[public_inputs],
+        rng,
+        &mut transcript,
+    )?;
+    Ok(transcript.finalize())
+}
+
+/// params and keys of the circuit computing the `n`-th term, generated once,
+/// so proofs for other seeds skip keygen
+/// note: the keys only depend on `k` and `n`, the seed is a witness
+pub struct FiboProver {
+    params: Params<EqAffine>,
+    pk: ProvingKey<EqAffine>,
+    n: usize,
+}
+
+impl FiboProver {
+    pub fn new(k: u32, n: usize) -> Result<Self, FiboError> {
+        check_capacity(k, n)?;
+        let params = params_for(k);
+        let circuit = FiboCircuit::<Fp>::empty(n);
+        let vk = keygen_vk(&params, &circuit)?;
+        let pk = keygen_pk(&params, vk, &circuit)?;
+        Ok(FiboProver { params, pk, n })
+    }
+
+    /// proof of `nth_fibonacci(n, a, b)` as public output
+    pub fn prove(&self, a: Fp, b: Fp) -> Result<Vec<u8>, FiboError> {
+        let circuit = FiboCircuit::<Fp> {
+            a: Some(a),
+            b: Some(b),
+            n: self.n,
+            output: None,
+            planner: PhantomData,
+        };
+        let output = [nth_fibonacci(self.n, a, b)];
+        prove_with_pk(&self.params, &self.pk, circuit, &[&output], OsRng, vec![])
+    }
+
+    pub fn verify(&self, public_inputs: &[Fp], proof: &[u8]) -> Result<(), FiboError> {
+        verify_with_params(&self.params, self.vk(), &[public_inputs], proof)
+    }
+
+    pub fn vk(&self) -> &VerifyingKey<EqAffine> {
+        self.pk.get_vk()
+    }
+}
+
+/// verify the proof bytes against the public inputs, grouped per instance column as in halo2:
+/// `public_inputs[i]` holds the rows of instance column `i`, so `FiboCircuit` takes `&[&[output]]`
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "verify",
+        skip(vk, public_inputs, proof),
+        fields(elapsed_ms = tracing::field::Empty)
+    )
+)]
+pub fn verify_fibonacci(
+    k: u32,
+    vk: &VerifyingKey<EqAffine>,
+    public_inputs: &[&[Fp]],
+    proof: &[u8],
+) -> Result<(), FiboError> {
+    #[cfg(feature = "tracing")]
+    let _elapsed = Elapsed::start();
+    verify_fibonacci_with_params(&params_for(k), vk, public_inputs, proof)
+}
+
+/// same as `verify_fibonacci`, but reuses already generated params
+pub fn verify_fibonacci_with_params(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    public_inputs: &[&[Fp]],
+    proof: &[u8],
+) -> Result<(), FiboError> {
+    verify_with_params(params, vk, public_inputs, proof)
+}
+
+/// `verify_fibonacci` with the vk rebuilt for the claimed `n`, so a proof of another term of the
+/// sequence doesn't verify as the `n`-th, whatever its output
+/// note: `n` needs no public input, the enabled selector rows and the copy of the output into
+/// the instance column are part of the vk, which the transcript starts from
+pub fn verify_fibonacci_n(k: u32, n: usize, output: Fp, proof: &[u8]) -> Result<(), FiboError> {
+    check_capacity(k, n)?;
+    let params = params_for(k);
+    let vk = keygen_vk(&params, &FiboCircuit::<Fp>::empty(n))?;
+    verify_with_params(&params, &vk, &[&[output]], proof)
+}
+
+/// `verify_fibonacci` of a proof read from `reader`, e.g. one of `prove_fibonacci_to_writer`
+pub fn verify_fibonacci_from_reader<R: Read>(
+    k: u32,
+    vk: &VerifyingKey<EqAffine>,
+    public_inputs: &[&[Fp]],
+    reader: R,
+) -> Result<(), FiboError> {
+    verify_with_params(&params_for(k), vk, public_inputs, reader)
+}
+
+fn verify_with_params<C: CurveAffine>(
+    params: &Params<C>,
+    vk: &VerifyingKey<C>,
+    public_inputs: &[&[C::Scalar]],
+    proof: impl Read,
+) -> Result<(), FiboError>
+where
+    C::Scalar: FromUniformBytes<64>,
+{
+    let strategy = SingleVerifier::new(params);
+    let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(proof);
+    Ok(verify_proof(
+        params,
+        vk,
+        strategy,
+        &[public_inputs],
+        &mut transcript,
+    )?)
+}
+
+/// prove the `n`-th term from (a, b) over `Fq`, the other field of the pasta cycle,
+/// returning the vk and the proof of `nth_fibonacci(n, a, b)` as public output
+/// note: `Fq` is the base field of Vesta, so this circuit does Vesta point arithmetic natively,
+/// which is what recursion over the `Fp` proofs needs. its commitments are on Pallas, whose
+/// scalar field is `Fq`, where `prove_fibonacci` commits on Vesta
+pub fn run_fibonacci_vesta(
+    k: u32,
+    a: Fq,
+    b: Fq,
+    n: usize,
+) -> Result<(VerifyingKey<EpAffine>, Vec<u8>), FiboError> {
+    let params = Params::<EpAffine>::new(k);
+    let circuit = FiboCircuit {
+        a: Some(a),
+        b: Some(b),
+        n,
+        output: None,
+        planner: PhantomData,
+    };
+    let vk = keygen_vk(&params, &circuit.without_witnesses())?;
+    let proof = prove_with_rng(&params, circuit, &[nth_fibonacci(n, a, b)], OsRng, vec![])?;
+    Ok((vk, proof))
+}
+
+/// `verify_fibonacci` of a `run_fibonacci_vesta` proof
+pub fn verify_fibonacci_vesta(
+    k: u32,
+    vk: &VerifyingKey<EpAffine>,
+    public_inputs: &[Fq],
+    proof: &[u8],
+) -> Result<(), FiboError> {
+    verify_with_params(&Params::new(k), vk, &[public_inputs], proof)
+}
+
+/// 32 byte blake2b hash identifying the vk, e.g. to tell a proof was made for another circuit
+/// before verifying it
+/// note: zcash halo2 has no vk serialization, so this hashes the pinned vk (domain, constraint
+/// system and commitments), the same data halo2 binds the vk into the transcript with
+pub fn vk_fingerprint<C: CurveAffine>(vk: &VerifyingKey<C>) -> [u8; 32] {
+    let pinned = format!("{:?}", vk.pinned());
+    let hash = blake2b_simd::Params::new()
+        .hash_length(32)
+        .personal(b"Fibo-vk-fprint")
+        .hash(pinned.as_bytes());
+    hash.as_bytes().try_into().unwrap()
+}
+
+/// verify many proofs of the same circuit at once, `inputs[i]` are the public inputs of `proofs[i]`
+/// note: the batch only tells that *some* proof is invalid, then the proofs are verified
+/// one by one to report the first failing index, so a bad batch costs a second pass
+pub fn verify_batch(
+    k: u32,
+    vk: &VerifyingKey<EqAffine>,
+    inputs: &[Vec<Fp>],
+    proofs: &[Vec<u8>],
+) -> Result<(), FiboError> {
+    if inputs.len() != proofs.len() {
+        return Err(FiboError::InvalidLength {
+            expected: inputs.len(),
+            got: proofs.len(),
+        });
+    }
+
+    let params = params_for(k);
+    let mut batch = BatchVerifier::new();
+    for (public_inputs, proof) in inputs.iter().zip(proofs) {
+        batch.add_proof(vec![vec![public_inputs.clone()]], proof.clone());
+    }
+    if batch.finalize(&params, vk) {
+        return Ok(());
+    }
+
+    for (index, (public_inputs, proof)) in inputs.iter().zip(proofs).enumerate() {
+        if verify_fibonacci_with_params(&params, vk, &[public_inputs], proof).is_err() {
+            return Err(FiboError::InvalidBatchProof { index });
+        }
+    }
+    // each proof verifies on its own, which is what counts
+    Ok(())
+}
+
+/// params, keys, proof and verification of the `n`-th term from (a, b) in one call,
+/// returns whether the proof verified
+/// note: params go through `load_or_generate_params` at `params_cache_path(k)`, so later calls
+/// (and processes) for the same `k` read them back instead of generating them
+pub fn prove_and_verify(k: u32, a: Fp, b: Fp, n: usize) -> Result<bool, FiboError> {
+    check_capacity(k, n)?;
+    let path = params_cache_path(k);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let params = load_or_generate_params(k, path)?;
+
+    let circuit = FiboCircuit {
+        a: Some(a),
+        b: Some(b),
+        n,
+        output: None,
+        planner: PhantomData,
+    };
+    let vk = keygen_vk(&params, &circuit.without_witnesses())?;
+    let output = [nth_fibonacci(n, a, b)];
+    let proof = prove_fibonacci_with_params(&params, circuit, &output)?;
+    Ok(verify_fibonacci_with_params(&params, &vk, &[&output], &proof).is_ok())
+}
+
+/// proof transcript bundled with its public inputs, so a verifier only needs one blob
+/// layout: | #inputs (u32 le) | inputs (32 bytes each) | proof len (u32 le) | proof |
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiboProof {
+    pub public_inputs: Vec<Fp>,
+    pub proof: Vec<u8>,
+}
+
+impl FiboProof {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + self.public_inputs.len() * 32 + self.proof.len());
+        bytes.extend_from_slice(&(self.public_inputs.len() as u32).to_le_bytes());
+        for input in &self.public_inputs {
+            bytes.extend_from_slice(input.to_repr().as_ref());
+        }
+        bytes.extend_from_slice(&(self.proof.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.proof);
+        bytes
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, FiboError> {
+        let num_inputs = read_u32(&mut bytes, "public input count")? as usize;
+        // check before allocating, a garbage prefix should not trigger a huge allocation
+        if num_inputs.saturating_mul(32) > bytes.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "blob truncated: {num_inputs} public inputs need {} bytes but only {} remain",
+                    num_inputs.saturating_mul(32),
+                    bytes.len()
+                ),
+            )
+            .into());
+        }
+
+        let mut public_inputs = Vec::with_capacity(num_inputs);
+        for i in 0..num_inputs {
+            let mut repr = <Fp as PrimeField>::Repr::default();
+            bytes.read_exact(repr.as_mut())?;
+            let input = Option::from(Fp::from_repr(repr)).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("public input {i} is not a canonical field element"),
+                )
+            })?;
+            public_inputs.push(input);
+        }
+
+        let proof_len = read_u32(&mut bytes, "proof length")? as usize;
+        if proof_len != bytes.len() {
+            return Err(FiboError::InvalidLength {
+                expected: proof_len,
+                got: bytes.len(),
+            });
+        }
+
+        Ok(FiboProof {
+            public_inputs,
+            proof: bytes.to_vec(),
+        })
+    }
+
+    pub fn verify(&self, k: u32, vk: &VerifyingKey<EqAffine>) -> Result<(), FiboError> {
+        verify_fibonacci(k, vk, &[&self.public_inputs], &self.proof)
+    }
+}
+
+/// `FiboProof` of the `n`-th term, a verifier can rebuild the vk from `n` alone
+/// layout: | n (u32 le) | `FiboProof::to_bytes` |
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FiboProofBundle {
+    pub n: usize,
+    pub proof: FiboProof,
+}
+
+impl FiboProofBundle {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = (self.n as u32).to_le_bytes().to_vec();
+        bytes.extend(self.proof.to_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, FiboError> {
+        let n = read_u32(&mut bytes, "sequence length")? as usize;
+        Ok(FiboProofBundle {
+            n,
+            proof: FiboProof::from_bytes(bytes)?,
+        })
+    }
+}
+
+fn read_u32(bytes: &mut &[u8], what: &str) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    bytes.read_exact(&mut buf).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("blob truncated while reading {what}"),
+        )
+    })?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FiboChip, FiboConfig, MulChip, MulConfig};
+    use halo2_proofs::circuit::Value;
+    #[cfg(feature = "tracing")]
+    use std::sync::{Arc, Mutex};
+
+    fn fibo_10th_proof() -> (VerifyingKey<EqAffine>, Vec<u8>) {
+        let circuit = FiboCircuit {
+            a: Some(Fp::from(1)),
+            b: Some(Fp::from(1)),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        let params = params_for(4);
+        let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+        let proof = prove_fibonacci(4, circuit, &[Fp::from(55)]).unwrap();
+        (vk, proof)
+    }
+
+    // the seed is public too, in its own instance column before the output one
+    #[derive(Default)]
+    struct PublicSeedCircuit {
+        seed: Option<(Fp, Fp)>,
+    }
+
+    impl Circuit<Fp> for PublicSeedCircuit {
+        type Config = (Column<Instance>, FiboConfig);
+        type FloorPlanner = halo2_proofs::circuit::SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let seed = meta.instance_column();
+            meta.enable_equality(seed);
+            (seed, FiboChip::configure_standalone(meta))
+        }
+
+        fn synthesize(
+            &self,
+            (seed, config): Self::Config,
+            mut layouter: impl halo2_proofs::circuit::Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboChip::construct(config);
+            let (a, b) = self.seed.unzip();
+            let cells = chip.assign_full_sequence(layouter.namespace(|| "sequence"), a, b, 10)?;
+            layouter.constrain_instance(cells[0].cell(), seed, 0)?;
+            layouter.constrain_instance(cells[1].cell(), seed, 1)?;
+            chip.expose_public(layouter.namespace(|| "out"), &cells[9], 0)
+        }
+    }
+
+    // a * b, exposed as public
+    #[derive(Default)]
+    struct ProductCircuit(Option<(Fp, Fp)>);
+
+    impl Circuit<Fp> for ProductCircuit {
+        type Config = (MulConfig, Column<Instance>);
+        type FloorPlanner = halo2_proofs::circuit::SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            let instance = meta.instance_column();
+            meta.enable_equality(instance);
+            (MulChip::configure_standalone(meta), instance)
+        }
+
+        fn synthesize(
+            &self,
+            (config, instance): Self::Config,
+            mut layouter: impl halo2_proofs::circuit::Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let [a, b] = [self.0.map(|(a, _)| a), self.0.map(|(_, b)| b)]
+                .map(|v| v.map_or(Value::unknown(), Value::known));
+            let chip = MulChip::construct(config);
+            let c = chip.assign_mul(layouter.namespace(|| "mul"), a, b)?;
+            layouter.constrain_instance(c.cell(), instance, 0)
+        }
+    }
+
+    #[test]
+    fn generic_prove_and_verify() {
+        let fibo = FiboCircuit::builder().length(10).build().unwrap();
+        let proof = prove(4, fibo, &[&[Fp::from(55)]]).unwrap();
+        let fibo = FiboCircuit::<Fp>::empty(10);
+        assert!(verify(4, &fibo, &[&[Fp::from(55)]], &proof).is_ok());
+        assert!(verify(4, &fibo, &[&[Fp::from(56)]], &proof).is_err());
+
+        let product = ProductCircuit(Some((Fp::from(6), Fp::from(7))));
+        let proof = prove(4, product, &[&[Fp::from(42)]]).unwrap();
+        assert!(verify(4, &ProductCircuit::default(), &[&[Fp::from(42)]], &proof).is_ok());
+        assert!(verify(4, &ProductCircuit::default(), &[&[Fp::from(41)]], &proof).is_err());
+        // a proof only verifies against the circuit it was made for
+        assert!(verify(4, &fibo, &[&[Fp::from(42)]], &proof).is_err());
+    }
+
+    #[test]
+    fn fibo_verify_two_instance_columns() {
+        let params = params_for(4);
+        let vk = keygen_vk(&params, &PublicSeedCircuit::default()).unwrap();
+        let pk = keygen_pk(&params, vk.clone(), &PublicSeedCircuit::default()).unwrap();
+        let (seed, out) = ([Fp::from(2), Fp::one()], [Fp::from(76)]);
+
+        let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+        let circuit = PublicSeedCircuit {
+            seed: Some((seed[0], seed[1])),
+        };
+        create_proof(
+            &params,
+            &pk,
+            &[circuit],
+            &[&[&seed, &out]],
+            OsRng,
+            &mut transcript,
+        )
+        .unwrap();
+        let proof = transcript.finalize();
+
+        assert!(verify_fibonacci(4, &vk, &[&seed, &out], &proof).is_ok());
+        assert!(verify_fibonacci(4, &vk, &[&seed, &[Fp::from(77)]], &proof).is_err());
+        // columns swapped
+        assert!(verify_fibonacci(4, &vk, &[&out, &seed], &proof).is_err());
+    }
+
+    #[test]
+    fn fibo_prove_and_verify() {
+        let (vk, proof) = fibo_10th_proof();
+        assert!(verify_fibonacci(4, &vk, &[&[Fp::from(55)]], &proof).is_ok());
+        // same proof doesn't verify against another public output
+        assert!(verify_fibonacci(4, &vk, &[&[Fp::from(56)]], &proof).is_err());
+    }
+
+    #[test]
+    fn fibo_tampered_proof_fails() {
+        let (vk, mut proof) = fibo_10th_proof();
+        let mid = proof.len() / 2;
+        proof[mid] ^= 1;
+        assert!(verify_fibonacci(4, &vk, &[&[Fp::from(55)]], &proof).is_err());
+    }
+
+    #[test]
+    fn fibo_proof_bytes_roundtrip() {
+        let (vk, proof) = fibo_10th_proof();
+        let fibo_proof = FiboProof {
+            public_inputs: vec![Fp::from(55)],
+            proof,
+        };
+
+        let bytes = fibo_proof.to_bytes();
+        let loaded = FiboProof::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded, fibo_proof);
+        assert!(loaded.verify(4, &vk).is_ok());
+    }
+
+    #[test]
+    fn fibo_proof_bytes_malformed() {
+        let (_, proof) = fibo_10th_proof();
+        let bytes = FiboProof {
+            public_inputs: vec![Fp::from(55)],
+            proof,
+        }
+        .to_bytes();
+
+        // truncated inside the proof, length prefix no longer matches
+        let err = FiboProof::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(matches!(err, FiboError::InvalidLength { expected, got } if got == expected - 1));
+
+        // truncated inside the public inputs
+        let err = FiboProof::from_bytes(&bytes[..10]).unwrap_err();
+        assert!(matches!(err, FiboError::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof));
+
+        // empty blob
+        let err = FiboProof::from_bytes(&[]).unwrap_err();
+        assert!(matches!(err, FiboError::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof));
+    }
+
+    #[test]
+    fn fibo_proof_bundle_roundtrip() {
+        let (_, proof) = fibo_10th_proof();
+        let bundle = FiboProofBundle {
+            n: 10,
+            proof: FiboProof {
+                public_inputs: vec![Fp::from(55)],
+                proof,
+            },
+        };
+        let bytes = bundle.to_bytes();
+        assert_eq!(bytes[..4], 10u32.to_le_bytes());
+        assert_eq!(FiboProofBundle::from_bytes(&bytes).unwrap(), bundle);
+        assert!(FiboProofBundle::from_bytes(&bytes[..3]).is_err());
+    }
+
+    #[test]
+    fn fibo_verify_batch_reports_failing_index() {
+        let params = params_for(4);
+        let circuit = |a: u64, b: u64| FiboCircuit {
+            a: Some(Fp::from(a)),
+            b: Some(Fp::from(b)),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        let vk = keygen_vk(&params, &circuit(1, 1).without_witnesses()).unwrap();
+
+        let mut inputs = vec![];
+        let mut proofs = vec![];
+        for (a, b) in [(1, 1), (2, 3), (1, 2), (5, 8)] {
+            let public_inputs = vec![nth_fibonacci(10, Fp::from(a), Fp::from(b))];
+            proofs
+                .push(prove_fibonacci_with_params(&params, circuit(a, b), &public_inputs).unwrap());
+            inputs.push(public_inputs);
+        }
+        assert!(verify_batch(4, &vk, &inputs[..3], &proofs[..3]).is_ok());
+
+        let mid = proofs[2].len() / 2;
+        proofs[2][mid] ^= 1;
+        let err = verify_batch(4, &vk, &inputs, &proofs).unwrap_err();
+        assert!(matches!(err, FiboError::InvalidBatchProof { index: 2 }));
+
+        let err = verify_batch(4, &vk, &inputs, &proofs[..3]).unwrap_err();
+        assert!(matches!(
+            err,
+            FiboError::InvalidLength {
+                expected: 4,
+                got: 3
+            }
+        ));
+    }
+
+    // quick version of the `fuzz_verify` target, see fuzz/
+    #[test]
+    fn fibo_garbage_proof_does_not_panic() {
+        let (vk, proof) = fibo_10th_proof();
+        let bytes = FiboProof {
+            public_inputs: vec![Fp::from(55)],
+            proof,
+        }
+        .to_bytes();
+
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        for _ in 0..32 {
+            // random bytes over a valid header, then a random truncation
+            let mut garbage = bytes.clone();
+            rng.fill_bytes(&mut garbage[40..]);
+            let len = rng.next_u32() as usize % garbage.len();
+            for blob in [&garbage[..], &garbage[..len]] {
+                if let Ok(proof) = FiboProof::from_bytes(blob) {
+                    assert!(proof.verify(4, &vk).is_err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fibo_prove_without_witness() {
+        let circuit = FiboCircuit::<Fp>::empty(10);
+        assert!(matches!(
+            prove_fibonacci(4, circuit, &[Fp::from(55)]),
+            Err(FiboError::MissingWitness)
+        ));
+    }
+
+    #[test]
+    fn fibo_keys_from_empty_circuit() {
+        let params = params_for(4);
+        let empty = FiboCircuit::<Fp>::empty(10);
+        let vk = keygen_vk(&params, &empty).unwrap();
+        let pk = keygen_pk(&params, vk.clone(), &empty).unwrap();
+
+        let circuit = FiboCircuit::builder()
+            .seed(Fp::from(2), Fp::one())
+            .build()
+            .unwrap();
+        assert_eq!(circuit.without_witnesses(), empty);
+        let proof =
+            prove_with_pk(&params, &pk, circuit, &[&[Fp::from(76)]], OsRng, vec![]).unwrap();
+        assert!(verify_fibonacci(4, &vk, &[&[Fp::from(76)]], &proof).is_ok());
+    }
+
+    #[test]
+    fn fibo_vk_fingerprint() {
+        let params = params_for(5);
+        let vk = |n| keygen_vk(&params, &FiboCircuit::<Fp>::empty(n)).unwrap();
+        assert_eq!(vk_fingerprint(&vk(10)), vk_fingerprint(&vk(10)));
+        assert_ne!(vk_fingerprint(&vk(10)), vk_fingerprint(&vk(11)));
+        // same n, other k
+        let other_k = keygen_vk(&params_for(4), &FiboCircuit::<Fp>::empty(10)).unwrap();
+        assert_ne!(vk_fingerprint(&vk(10)), vk_fingerprint(&other_k));
+    }
+
+    #[test]
+    fn fibo_proof_through_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("proof.bin");
+        let circuit = FiboCircuit::builder().build().unwrap();
+        let vk = keygen_vk(&params_for(4), &circuit.without_witnesses()).unwrap();
+
+        let file = io::BufWriter::new(fs::File::create(&path).unwrap());
+        let mut file = prove_fibonacci_to_writer(4, circuit, &[Fp::from(55)], file).unwrap();
+        file.flush().unwrap();
+        drop(file);
+
+        let proof = || io::BufReader::new(fs::File::open(&path).unwrap());
+        assert!(verify_fibonacci_from_reader(4, &vk, &[&[Fp::from(55)]], proof()).is_ok());
+        assert!(verify_fibonacci_from_reader(4, &vk, &[&[Fp::from(56)]], proof()).is_err());
+        // the file holds plain proof bytes
+        let bytes = fs::read(&path).unwrap();
+        assert!(verify_fibonacci(4, &vk, &[&[Fp::from(55)]], &bytes).is_ok());
+    }
+
+    #[test]
+    fn fibo_deterministic_proofs() {
+        let circuit = || FiboCircuit::builder().build().unwrap();
+        let vk = keygen_vk(&params_for(4), &circuit().without_witnesses()).unwrap();
+        let prove = |seed| prove_fibonacci_deterministic(4, circuit(), &[Fp::from(55)], seed);
+
+        let (first, again, other) = (prove(1).unwrap(), prove(1).unwrap(), prove(2).unwrap());
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+        for proof in [first, other] {
+            assert!(verify_fibonacci(4, &vk, &[&[Fp::from(55)]], &proof).is_ok());
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn fibo_parallel_proofs_are_identical() {
+        let params = params_for(4);
+        let circuit = || FiboCircuit::builder().build().unwrap();
+        let vk = keygen_vk(&params, &circuit().without_witnesses()).unwrap();
+
+        let proofs: Vec<_> = [1, 4]
+            .into_iter()
+            .map(|threads| {
+                let rng = ChaCha20Rng::seed_from_u64(42);
+                prove_fibonacci_parallel(&params, circuit(), &[Fp::from(55)], threads, rng).unwrap()
+            })
+            .collect();
+
+        assert_eq!(proofs[0], proofs[1]);
+        assert!(verify_fibonacci_with_params(&params, &vk, &[&[Fp::from(55)]], &proofs[0]).is_ok());
+    }
+
+    #[test]
+    fn fibo_params_cache_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("params_k4.bin");
+
+        // first call generates and writes, second call reads the cache
+        load_or_generate_params(4, &path).unwrap();
+        assert!(path.exists());
+        let params = load_or_generate_params(4, &path).unwrap();
+
+        let (_, proof) = fibo_10th_proof();
+        let vk = keygen_vk(&params, &FiboCircuit::<Fp>::empty(10)).unwrap();
+        assert!(verify_fibonacci(4, &vk, &[&[Fp::from(55)]], &proof).is_ok());
+
+        // cache for another k is rejected
+        let err = load_or_generate_params(5, &path).unwrap_err();
+        assert!(matches!(err, FiboError::Io(e) if e.kind() == io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn fibo_params_unwritable_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("params.bin");
+        assert!(load_or_generate_params(4, path).is_err());
+    }
+
+    #[test]
+    fn fibo_verify_checks_n() {
+        let (_, proof) = fibo_10th_proof();
+        assert!(verify_fibonacci_n(4, 10, Fp::from(55), &proof).is_ok());
+        // keys for the 20th term, with either output
+        assert!(verify_fibonacci_n(4, 20, Fp::from(55), &proof).is_err());
+        assert!(verify_fibonacci_n(4, 20, Fp::from(6765), &proof).is_err());
+
+        // same rows, the output is copied from another cell
+        let circuit = FiboCircuit::builder().length(3).build().unwrap();
+        let proof = prove_fibonacci(4, circuit, &[Fp::from(2)]).unwrap();
+        assert!(verify_fibonacci_n(4, 3, Fp::from(2), &proof).is_ok());
+        assert!(verify_fibonacci_n(4, 2, Fp::from(2), &proof).is_err());
+    }
+
+    #[test]
+    fn fibo_prover_reuses_keys() {
+        let prover = FiboProver::new(4, 10).unwrap();
+        for (a, b) in [(1, 1), (2, 3), (5, 8)] {
+            let (a, b) = (Fp::from(a), Fp::from(b));
+            let proof = prover.prove(a, b).unwrap();
+            let output = nth_fibonacci(10, a, b);
+            assert!(prover.verify(&[output], &proof).is_ok());
+            assert!(prover.verify(&[output + Fp::one()], &proof).is_err());
+        }
+    }
+
+    #[test]
+    fn fibo_prove_and_verify_vesta() {
+        let (vk, proof) = run_fibonacci_vesta(4, Fq::one(), Fq::one(), 10).unwrap();
+        assert!(verify_fibonacci_vesta(4, &vk, &[Fq::from(55)], &proof).is_ok());
+        assert!(verify_fibonacci_vesta(4, &vk, &[Fq::from(56)], &proof).is_err());
+
+        // same circuit and output, but the `Fp` verifier reads Vesta points from the proof
+        let (fp_vk, _) = fibo_10th_proof();
+        assert!(verify_fibonacci(4, &fp_vk, &[&[Fp::from(55)]], &proof).is_err());
+    }
+
+    #[test]
+    fn fibo_prove_and_verify_one_shot() {
+        assert!(prove_and_verify(4, Fp::one(), Fp::one(), 10).unwrap());
+        // second call reads the params of k = 4 back from the cache file
+        assert!(params_cache_path(4).exists());
+        assert!(prove_and_verify(4, Fp::from(2), Fp::from(3), 12).unwrap());
+
+        // 28 rows don't fit in 16, caught before the params are generated
+        assert!(matches!(
+            prove_and_verify(4, Fp::one(), Fp::one(), 30),
+            Err(FiboError::InvalidLength {
+                expected: 12,
+                got: 30
+            })
+        ));
+    }
+
+    // span names with their fields, as `name=value`, and the stack of entered spans
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct SpanRecorder {
+        spans: Arc<Mutex<Vec<RecordedSpan>>>,
+        entered: Arc<Mutex<Vec<tracing::span::Id>>>,
+    }
+
+    #[cfg(feature = "tracing")]
+    type RecordedSpan = (&'static tracing::Metadata<'static>, Vec<String>);
+
+    #[cfg(feature = "tracing")]
+    impl SpanRecorder {
+        fn fields_of(&self, name: &str) -> Vec<String> {
+            let spans = self.spans.lock().unwrap();
+            let span = spans.iter().find(|(span, _)| span.name() == name);
+            span.map(|(_, fields)| fields.clone()).unwrap_or_default()
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    struct Fields<'a>(&'a mut Vec<String>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::field::Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.push(format!("{}={value:?}", field.name()));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanRecorder {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut fields = vec![];
+            span.record(&mut Fields(&mut fields));
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((span.metadata(), fields));
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, id: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            values.record(&mut Fields(&mut spans[id.into_u64() as usize - 1].1));
+        }
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+        fn event(&self, _: &tracing::Event<'_>) {}
+
+        fn enter(&self, id: &tracing::span::Id) {
+            self.entered.lock().unwrap().push(id.clone());
+        }
+
+        fn exit(&self, _: &tracing::span::Id) {
+            self.entered.lock().unwrap().pop();
+        }
+
+        // needed by `Span::current`, which records `elapsed_ms`
+        fn current_span(&self) -> tracing_core::span::Current {
+            match self.entered.lock().unwrap().last() {
+                Some(id) => {
+                    let metadata = self.spans.lock().unwrap()[id.into_u64() as usize - 1].0;
+                    tracing_core::span::Current::new(id.clone(), metadata)
+                }
+                None => tracing_core::span::Current::none(),
+            }
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn fibo_prove_span() {
+        let recorder = SpanRecorder::default();
+        let (vk, proof) = tracing::subscriber::with_default(recorder.clone(), fibo_10th_proof);
+
+        let prove = recorder.fields_of("prove");
+        assert!(prove.contains(&"k=4".to_string()), "{prove:?}");
+        assert!(prove.contains(&"n=10".to_string()), "{prove:?}");
+        assert!(
+            prove.iter().any(|f| f.starts_with("elapsed_ms=")),
+            "{prove:?}"
+        );
+        assert!(recorder.fields_of("params").contains(&"k=4".to_string()));
+
+        tracing::subscriber::with_default(recorder.clone(), || {
+            verify_fibonacci(4, &vk, &[&[Fp::from(55)]], &proof).unwrap()
+        });
+        assert!(recorder.fields_of("verify").contains(&"k=4".to_string()));
+    }
+}
@@ -0,0 +1,121 @@
+use halo2_proofs::{circuit::*, pasta::group::ff::PrimeField, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// config of the range check: one advice column holding the value
+#[derive(Clone, Debug)]
+pub struct RangeConfig {
+    pub advice: Column<Advice>,
+    pub selector: Selector,
+}
+
+/// constrains a value to `0 <= value < RANGE`
+pub struct RangeCheckChip<F: PrimeField, const RANGE: usize> {
+    config: RangeConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: PrimeField, const RANGE: usize> RangeCheckChip<F, RANGE> {
+    pub fn construct(config: RangeConfig) -> Self {
+        RangeCheckChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> RangeConfig {
+        let advice = meta.advice_column();
+        let selector = meta.selector();
+
+        meta.enable_equality(advice);
+
+        meta.create_gate("range check", |meta| {
+            // | advice | selector |
+            // |  value |     s    |
+            let s = meta.query_selector(selector);
+            let value = meta.query_advice(advice, Rotation::cur());
+
+            // value * (1 - value) * (2 - value) * ... * (RANGE - 1 - value) = 0
+            // note: degree of the gate grows with RANGE, so this only suits small ranges
+            let range_check = (1..RANGE).fold(value.clone(), |expr, i| {
+                expr * (Expression::Constant(F::from(i as u64)) - value.clone())
+            });
+
+            vec![s * range_check]
+        });
+
+        RangeConfig { advice, selector }
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Value<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "range check",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                region
+                    .assign_advice(|| "value", self.config.advice, 0, || value)
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    struct RangeCheckCircuit<F: PrimeField, const RANGE: usize> {
+        value: Value<F>,
+    }
+
+    impl<F: PrimeField, const RANGE: usize> Circuit<F> for RangeCheckCircuit<F, RANGE> {
+        type Config = RangeConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                value: Value::unknown(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            RangeCheckChip::<F, RANGE>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let chip = RangeCheckChip::<F, RANGE>::construct(config);
+            chip.assign(layouter.namespace(|| "value"), self.value)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn range_check_in_range() {
+        let circuit = RangeCheckCircuit::<Fp, 8> {
+            value: Value::known(Fp::from(3)),
+        };
+        MockProver::run(4, &circuit, vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn range_check_out_of_range() {
+        let circuit = RangeCheckCircuit::<Fp, 8> {
+            value: Value::known(Fp::from(8)),
+        };
+        let prover = MockProver::run(4, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
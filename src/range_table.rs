@@ -0,0 +1,262 @@
+use halo2_proofs::{circuit::*, pasta::group::ff::PrimeField, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// config of the table range check: the value and the table of allowed values
+#[derive(Clone, Debug)]
+pub struct RangeTableConfig {
+    pub advice: Column<Advice>,
+    pub selector: Selector,
+    pub table: TableColumn,
+}
+
+/// constrains a value to `0 <= value < 2^BITS` by a lookup
+/// note: unlike `RangeCheckChip`, the degree stays the same for any range,
+/// the table costs `2^BITS` rows instead, so `k` must be above `BITS`
+pub struct RangeTableChip<F: PrimeField, const BITS: usize> {
+    config: RangeTableConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: PrimeField, const BITS: usize> RangeTableChip<F, BITS> {
+    pub fn construct(config: RangeTableConfig) -> Self {
+        RangeTableChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> RangeTableConfig {
+        let advice = meta.advice_column();
+        // note: lookups need a complex selector, a simple one could be combined with others
+        let selector = meta.complex_selector();
+        let table = meta.lookup_table_column();
+
+        meta.enable_equality(advice);
+
+        meta.lookup(|meta| {
+            // | advice | selector | table |
+            // |  value |     s    |   i   |
+            let s = meta.query_selector(selector);
+            let value = meta.query_advice(advice, Rotation::cur());
+
+            // rows without the selector look up 0, which is in the table
+            vec![(s * value, table)]
+        });
+
+        RangeTableConfig {
+            advice,
+            selector,
+            table,
+        }
+    }
+
+    /// fill the table with `0..2^BITS`, once per circuit
+    pub fn load_table(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "range table",
+            |mut table| {
+                for i in 0..1 << BITS {
+                    table.assign_cell(
+                        || "value",
+                        self.config.table,
+                        i,
+                        || Value::known(F::from(i as u64)),
+                    )?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    pub fn assign(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Value<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "range check",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                region
+                    .assign_advice(|| "value", self.config.advice, 0, || value)
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auto_k, circuit_blinding_factors, cost_max_degree, min_k_with_blinding, nth_fibonacci,
+        FiboChip, FiboCircuit, FiboConfig, RangeCheckChip, RangeConfig,
+    };
+    use halo2_proofs::{
+        dev::{CircuitCost, MockProver, VerifyFailure},
+        pasta::{Eq, Fp},
+    };
+
+    #[derive(Debug)]
+    struct RangeTableCircuit<const BITS: usize> {
+        value: Value<Fp>,
+    }
+
+    impl<const BITS: usize> Circuit<Fp> for RangeTableCircuit<BITS> {
+        type Config = RangeTableConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                value: Value::unknown(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            RangeTableChip::<Fp, BITS>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = RangeTableChip::<Fp, BITS>::construct(config);
+            chip.load_table(layouter.namespace(|| "table"))?;
+            chip.assign(layouter.namespace(|| "value"), self.value)?;
+            Ok(())
+        }
+    }
+
+    // same check with the product gate of `RangeCheckChip`
+    #[derive(Debug)]
+    struct RangeProductCircuit<const RANGE: usize>;
+
+    impl<const RANGE: usize> Circuit<Fp> for RangeProductCircuit<RANGE> {
+        type Config = RangeConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            RangeCheckChip::<Fp, RANGE>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = RangeCheckChip::<Fp, RANGE>::construct(config);
+            chip.assign(layouter.namespace(|| "value"), Value::unknown())?;
+            Ok(())
+        }
+    }
+
+    // 2^10 table rows + 6 reserved
+    const K: u32 = 11;
+
+    #[test]
+    fn range_table_10_bits() {
+        let circuit = RangeTableCircuit::<10> {
+            value: Value::known(Fp::from(1000)),
+        };
+        MockProver::run(K, &circuit, vec![])
+            .unwrap()
+            .assert_satisfied();
+
+        let circuit = RangeTableCircuit::<10> {
+            value: Value::known(Fp::from(2000)),
+        };
+        let failures = MockProver::run(K, &circuit, vec![])
+            .unwrap()
+            .verify()
+            .unwrap_err();
+        assert!(failures
+            .iter()
+            .all(|f| matches!(f, VerifyFailure::Lookup { .. })));
+    }
+
+    #[test]
+    fn range_table_cheaper_than_product() {
+        let table = CircuitCost::<Eq, _>::measure(
+            K,
+            &RangeTableCircuit::<10> {
+                value: Value::unknown(),
+            },
+        );
+        let product = CircuitCost::<Eq, _>::measure(K, &RangeProductCircuit::<1024>);
+
+        // the product gate has degree RANGE + 1, every degree above 2 adds a commitment,
+        // the lookup stays at 5 for any range
+        assert_eq!(cost_max_degree(&table), 5);
+        assert_eq!(cost_max_degree(&product), 1025);
+        assert!(usize::from(table.proof_size(1)) * 10 < usize::from(product.proof_size(1)));
+    }
+
+    // `FiboCircuit`, with the output range checked by the table
+    #[derive(Debug)]
+    struct FiboRangeCircuit<const BITS: usize>(usize);
+
+    impl<const BITS: usize> Circuit<Fp> for FiboRangeCircuit<BITS> {
+        type Config = (FiboConfig, RangeTableConfig);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            (
+                FiboChip::configure_standalone(meta),
+                RangeTableChip::<Fp, BITS>::configure(meta),
+            )
+        }
+
+        fn synthesize(
+            &self,
+            (fibo, range): Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let fibo_chip = FiboChip::construct(fibo);
+            let range_chip = RangeTableChip::<Fp, BITS>::construct(range);
+            range_chip.load_table(layouter.namespace(|| "table"))?;
+
+            let cells = fibo_chip.assign_sequence_single_region(
+                layouter.namespace(|| "sequence"),
+                Some(Fp::one()),
+                Some(Fp::one()),
+                self.0,
+            )?;
+            let out = cells.last().unwrap();
+            range_chip.assign(layouter.namespace(|| "range"), out.value().copied())?;
+            fibo_chip.expose_public(layouter.namespace(|| "out"), out, 0)
+        }
+    }
+
+    #[test]
+    fn range_table_needs_larger_k() {
+        let n = 10;
+        let instance = vec![vec![nth_fibonacci(n, Fp::one(), Fp::one())]];
+
+        // same blinding rows as the add circuit, the table is what doesn't fit
+        let blinding = circuit_blinding_factors::<Fp, FiboRangeCircuit<6>>();
+        assert_eq!(blinding, circuit_blinding_factors::<Fp, FiboCircuit<Fp>>());
+        let k = min_k_with_blinding(n, blinding);
+        assert_eq!(k, auto_k(n));
+        assert!(matches!(
+            MockProver::run(k, &FiboRangeCircuit::<6>(n), instance.clone()),
+            Err(Error::NotEnoughRowsAvailable { .. })
+        ));
+
+        // 2^6 table rows + 6 reserved
+        MockProver::run(7, &FiboRangeCircuit::<6>(n), instance)
+            .unwrap()
+            .assert_satisfied();
+    }
+}
@@ -0,0 +1,295 @@
+//! random linear combination with a challenge, enabled by the `kzg` feature
+//! note: zcash halo2 0.3 has no challenge API (a single phase only), so like `kzg.rs`
+//! this is built on the axiom fork, and takes its cells instead of `ACell`
+use halo2_axiom::{
+    circuit::{Layouter, Value},
+    halo2curves::ff::Field,
+    plonk::{
+        Advice, Challenge, Column, ConstraintSystem, Error, FirstPhase, SecondPhase, Selector,
+    },
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+use crate::ACellKzg;
+
+/// config of the rlc: the values, copied in the first phase, and the running sum in the second
+#[derive(Clone, Debug)]
+pub struct RlcConfig {
+    pub value: Column<Advice>,
+    pub acc: Column<Advice>,
+    pub first: Selector,
+    pub next: Selector,
+    /// squeezed from the transcript once the first phase columns are committed
+    pub challenge: Challenge,
+}
+
+/// `v_0 * r^(n-1) + v_1 * r^(n-2) + ... + v_(n-1)` of the values `v` and the challenge `r`,
+/// see `rlc` for the same on the host
+pub struct RlcChip<F: Field> {
+    config: RlcConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> RlcChip<F> {
+    pub fn construct(config: RlcConfig) -> Self {
+        RlcChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> RlcConfig {
+        let value = meta.advice_column_in(FirstPhase);
+        // note: the running sum depends on the challenge, so it can only be assigned after it
+        let acc = meta.advice_column_in(SecondPhase);
+        let challenge = meta.challenge_usable_after(FirstPhase);
+        let first = meta.selector();
+        let next = meta.selector();
+
+        meta.enable_equality(value);
+        meta.enable_equality(acc);
+
+        meta.create_gate("rlc first", |meta| {
+            // | value | acc | first |
+            // |  v_0  | v_0 |   s   |
+            let s = meta.query_selector(first);
+            let value = meta.query_advice(value, Rotation::cur());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            vec![s * (acc - value)]
+        });
+
+        meta.create_gate("rlc next", |meta| {
+            // | value |       acc         | next |
+            // |       |      acc_prev     |      |
+            // |  v_i  | acc_prev * r + v_i |  s   |
+            let s = meta.query_selector(next);
+            let value = meta.query_advice(value, Rotation::cur());
+            let acc_prev = meta.query_advice(acc, Rotation::prev());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            let r = meta.query_challenge(challenge);
+            vec![s * (acc - (acc_prev * r + value))]
+        });
+
+        RlcConfig {
+            value,
+            acc,
+            first,
+            next,
+            challenge,
+        }
+    }
+
+    /// copy the cells into one region and fold them with the challenge, returning the result cell
+    /// note: this moves the circuit to the second phase, every first phase cell
+    /// (e.g. the whole fibonacci sequence) must be assigned before
+    pub fn assign_rlc<'v>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cells: &[ACellKzg<'_, F>],
+    ) -> Result<ACellKzg<'v, F>, Error> {
+        // no row to enable the first selector on
+        if cells.is_empty() {
+            return Err(Error::Synthesis);
+        }
+        layouter.assign_region(
+            || "rlc",
+            |mut region| {
+                // copy constraint
+                for (row, cell) in cells.iter().enumerate() {
+                    cell.copy_advice(&mut region, self.config.value, row);
+                }
+
+                // commits the values, the challenge is known from here on
+                region.next_phase();
+                let r = region.get_challenge(self.config.challenge);
+
+                let mut acc = Value::known(F::ZERO);
+                let mut acc_cell = None;
+                for (row, cell) in cells.iter().enumerate() {
+                    if row == 0 {
+                        self.config.first.enable(&mut region, row)?;
+                    } else {
+                        self.config.next.enable(&mut region, row)?;
+                    }
+                    acc = acc * r + cell.value().map(|v| v.evaluate());
+                    acc_cell = Some(region.assign_advice(self.config.acc, row, acc));
+                }
+                Ok(acc_cell.expect("cells are not empty"))
+            },
+        )
+    }
+}
+
+/// `RlcChip` on the host, for a challenge read back from the prover
+pub fn rlc<F: Field>(values: &[F], r: F) -> F {
+    values.iter().fold(F::ZERO, |acc, v| acc * r + v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nth_fibonacci, FiboCircuit, FiboConfigKzg};
+    use halo2_axiom::{
+        circuit::SimpleFloorPlanner,
+        dev::MockProver,
+        halo2curves::bn256::{Bn256, Fr, G1Affine},
+        plonk::{create_proof, keygen_pk, keygen_vk, verify_proof, Circuit},
+        poly::kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{ProverSHPLONK, VerifierSHPLONK},
+            strategy::SingleStrategy,
+        },
+        transcript::{
+            Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+        },
+    };
+    use rand_core::OsRng;
+    use std::{cell::RefCell, rc::Rc};
+
+    // terms, challenge and rlc as synthesize saw them
+    type Seen = (Vec<Fr>, Fr, Fr);
+
+    // the sequence of `FiboCircuit`, folded by the rlc chip
+    struct FiboRlcCircuit {
+        fibo: FiboCircuit<Fr>,
+        seen: Rc<RefCell<Option<Seen>>>,
+    }
+
+    impl Circuit<Fr> for FiboRlcCircuit {
+        type Config = (FiboConfigKzg, RlcConfig);
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                fibo: self.fibo.without_witnesses(),
+                seen: Rc::default(),
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            (FiboCircuit::configure(meta), RlcChip::configure(meta))
+        }
+
+        fn synthesize(
+            &self,
+            (fibo, rlc_config): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let cells = self
+                .fibo
+                .assign_sequence_kzg(&fibo, layouter.namespace(|| "sequence"))?;
+            layouter.constrain_instance(cells.last().unwrap().cell(), fibo.instance, 0);
+
+            let chip = RlcChip::construct(rlc_config.clone());
+            let out = chip.assign_rlc(layouter.namespace(|| "rlc"), &cells)?;
+
+            let terms: Value<Vec<Fr>> = cells
+                .iter()
+                .map(|c| c.value().map(|v| v.evaluate()))
+                .collect();
+            terms
+                .zip(layouter.get_challenge(rlc_config.challenge))
+                .zip(out.value().map(|v| v.evaluate()))
+                .map(|((terms, r), out)| *self.seen.borrow_mut() = Some((terms, r, out)));
+            Ok(())
+        }
+    }
+
+    fn fibo_10th_rlc() -> FiboRlcCircuit {
+        FiboRlcCircuit {
+            fibo: FiboCircuit {
+                a: Some(Fr::one()),
+                b: Some(Fr::one()),
+                n: 10,
+                output: None,
+                planner: PhantomData,
+            },
+            seen: Rc::default(),
+        }
+    }
+
+    #[test]
+    fn rlc_of_fibonacci_mock() {
+        let circuit = fibo_10th_rlc();
+        let expected = nth_fibonacci(10, Fr::one(), Fr::one());
+        MockProver::run(5, &circuit, vec![vec![expected]])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    // folds no cell at all
+    struct EmptyRlcCircuit;
+
+    impl Circuit<Fr> for EmptyRlcCircuit {
+        type Config = RlcConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            RlcChip::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            RlcChip::construct(config).assign_rlc(layouter, &[])?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn rlc_of_no_values_is_rejected() {
+        assert!(matches!(
+            MockProver::run(5, &EmptyRlcCircuit, vec![]),
+            Err(Error::Synthesis)
+        ));
+    }
+
+    #[test]
+    fn rlc_matches_host_with_transcript_challenge() {
+        let params = ParamsKZG::<Bn256>::setup(5, OsRng);
+        let circuit = fibo_10th_rlc();
+        let seen = circuit.seen.clone();
+        let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+        let pk = keygen_pk(&params, vk, &circuit.without_witnesses()).unwrap();
+        let instance = [nth_fibonacci(10, Fr::one(), Fr::one())];
+
+        let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+        create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<'_, Bn256>, _, _, _, _>(
+            &params,
+            &pk,
+            &[circuit],
+            &[&[&instance]],
+            OsRng,
+            &mut transcript,
+        )
+        .unwrap();
+        let proof = transcript.finalize();
+
+        // the challenge the prover squeezed after committing the sequence
+        let (terms, r, out) = seen.borrow().clone().unwrap();
+        assert_eq!(terms.len(), 10);
+        assert_eq!(terms[9], instance[0]);
+        assert_eq!(out, rlc(&terms, r));
+
+        let mut transcript = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(&proof[..]);
+        assert!(
+            verify_proof::<KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'_, Bn256>, _, _, _>(
+                &params,
+                pk.get_vk(),
+                SingleStrategy::new(&params),
+                &[&[&instance]],
+                &mut transcript,
+            )
+            .is_ok()
+        );
+    }
+}
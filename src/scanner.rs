@@ -0,0 +1,116 @@
+use halo2_proofs::{
+    pasta::Fp,
+    plonk::{Any, Circuit, Error},
+};
+
+use crate::CellRecorder;
+
+/// highest assigned row of every column after synthesis, `None` if never assigned,
+/// indexed by column index as in the constraint system
+/// note: selectors are listed by themselves, they are only compressed into fixed columns at keygen
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnScan {
+    pub advice: Vec<Option<usize>>,
+    pub fixed: Vec<Option<usize>>,
+    /// rows given to `scan_columns`
+    pub instance: Vec<Option<usize>>,
+    /// highest row the selector is enabled on
+    pub selectors: Vec<Option<usize>>,
+}
+
+impl ColumnScan {
+    /// `(type, index)` of the columns without any assigned cell
+    pub fn unused_columns(&self) -> Vec<(Any, usize)> {
+        [
+            (Any::Advice, &self.advice),
+            (Any::Fixed, &self.fixed),
+            (Any::Instance, &self.instance),
+        ]
+        .into_iter()
+        .flat_map(|(column_type, rows)| {
+            rows.iter()
+                .enumerate()
+                .filter(|(_, max_row)| max_row.is_none())
+                .map(move |(index, _)| (column_type, index))
+        })
+        .collect()
+    }
+
+    /// indices of the selectors never enabled
+    pub fn unused_selectors(&self) -> Vec<usize> {
+        (0..self.selectors.len())
+            .filter(|&i| self.selectors[i].is_none())
+            .collect()
+    }
+}
+
+/// synthesize the circuit and scan the cells it assigns, for capacity planning
+pub fn scan_columns<C: Circuit<Fp>>(
+    k: u32,
+    circuit: &C,
+    instance: Vec<Vec<Fp>>,
+) -> Result<ColumnScan, Error> {
+    let instance_rows = instance
+        .iter()
+        .map(|rows| rows.len().checked_sub(1))
+        .collect();
+    let cells = CellRecorder::record(k, circuit, instance)?;
+    let assigned = |columns: Vec<Vec<Option<Fp>>>| {
+        let used = |rows: Vec<Option<Fp>>| rows.iter().map(Option::is_some).collect();
+        max_rows(columns.into_iter().map(used).collect())
+    };
+    Ok(ColumnScan {
+        advice: assigned(cells.advice),
+        fixed: assigned(cells.fixed),
+        instance: instance_rows,
+        selectors: max_rows(cells.selectors),
+    })
+}
+
+fn max_rows(columns: Vec<Vec<bool>>) -> Vec<Option<usize>> {
+    columns
+        .into_iter()
+        .map(|rows| rows.iter().rposition(|&used| used))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nth_fibonacci, FiboChip, FiboCircuit, FiboConstantSeedCircuit};
+
+    #[test]
+    fn fibo_scan_columns() {
+        for n in [3, 10, 12] {
+            let circuit = FiboCircuit::<Fp>::builder().length(n).build().unwrap();
+            let output = nth_fibonacci(n, Fp::one(), Fp::one());
+            let scan = scan_columns(4, &circuit, vec![vec![output]]).unwrap();
+
+            // col_c holds the terms 3..=n
+            assert_eq!(scan.advice[2], Some(n - 3), "n = {n}");
+            let last_row = FiboChip::<Fp>::rows_for(n) - 1;
+            assert_eq!(scan.advice, [Some(last_row); 3]);
+            assert_eq!(scan.instance, [Some(0)]);
+            // the constants column is only used by the constant seed layouts
+            assert_eq!(scan.unused_columns(), [(Any::Fixed, 0)]);
+        }
+
+        // a single row never checks a next row
+        let circuit = FiboCircuit::<Fp>::builder().length(3).build().unwrap();
+        let scan = scan_columns(4, &circuit, vec![vec![Fp::from(2)]]).unwrap();
+        assert_eq!(scan.selectors, [Some(0), None]);
+        assert_eq!(scan.unused_selectors(), [1]);
+    }
+
+    #[test]
+    fn fibo_scan_constants() {
+        // the seed is copied from the constants column
+        let scan = scan_columns(
+            4,
+            &FiboConstantSeedCircuit::new(10),
+            vec![vec![Fp::from(55)]],
+        )
+        .unwrap();
+        assert_eq!(scan.fixed, [Some(1)]);
+    }
+}
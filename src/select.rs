@@ -0,0 +1,174 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// config of the mux: `out = cond ? a : b`
+#[derive(Clone, Debug)]
+pub struct SelectConfig {
+    pub cond: Column<Advice>,
+    pub advice: [Column<Advice>; 2],
+    pub out: Column<Advice>,
+    pub selector: Selector,
+}
+
+pub struct SelectChip<F: Field> {
+    config: SelectConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> SelectChip<F> {
+    pub fn construct(config: SelectConfig) -> Self {
+        SelectChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> SelectConfig {
+        let cond = meta.advice_column();
+        let col_a = meta.advice_column();
+        let col_b = meta.advice_column();
+        let out = meta.advice_column();
+        let selector = meta.selector();
+
+        meta.enable_equality(cond);
+        meta.enable_equality(col_a);
+        meta.enable_equality(col_b);
+        meta.enable_equality(out);
+
+        meta.create_gate("select", |meta| {
+            // | cond | col_a | col_b | out | selector |
+            // |  c   |   a   |   b   |  o  |     s    |
+            let s = meta.query_selector(selector);
+            let c = meta.query_advice(cond, Rotation::cur());
+            let a = meta.query_advice(col_a, Rotation::cur());
+            let b = meta.query_advice(col_b, Rotation::cur());
+            let o = meta.query_advice(out, Rotation::cur());
+
+            let one_minus_c = Expression::Constant(F::ONE) - c.clone();
+            vec![
+                // cond is a boolean
+                s.clone() * c.clone() * one_minus_c.clone(),
+                s * (o - c * a - one_minus_c * b),
+            ]
+        });
+
+        SelectConfig {
+            cond,
+            advice: [col_a, col_b],
+            out,
+            selector,
+        }
+    }
+
+    /// `a` and `b` are copied in from their regions, returns `a` if cond is 1 and `b` if 0
+    pub fn select(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cond: Value<F>,
+        a: &ACell<F>,
+        b: &ACell<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "select",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                region.assign_advice(|| "cond", self.config.cond, 0, || cond)?;
+                a.0.copy_advice(|| "a", &mut region, self.config.advice[0], 0)?;
+                b.0.copy_advice(|| "b", &mut region, self.config.advice[1], 0)?;
+
+                let out = cond
+                    .zip(a.value().copied())
+                    .zip(b.value().copied())
+                    .map(|((c, a), b)| c * a + (F::ONE - c) * b);
+                region
+                    .assign_advice(|| "out", self.config.out, 0, || out)
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+
+    struct SelectCircuit {
+        cond: Value<Fp>,
+        a: Value<Fp>,
+        b: Value<Fp>,
+        expected: Fp,
+    }
+
+    impl Circuit<Fp> for SelectCircuit {
+        type Config = SelectConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                cond: Value::unknown(),
+                a: Value::unknown(),
+                b: Value::unknown(),
+                expected: self.expected,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            SelectChip::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            // inputs come from another region, like the output of a previous chip
+            let (a, b) = layouter.assign_region(
+                || "inputs",
+                |mut region| {
+                    let a = region.assign_advice(|| "a", config.advice[0], 0, || self.a)?;
+                    let b = region.assign_advice(|| "b", config.advice[1], 0, || self.b)?;
+                    Ok((ACell(a), ACell(b)))
+                },
+            )?;
+
+            let chip = SelectChip::construct(config);
+            let out = chip.select(layouter.namespace(|| "select"), self.cond, &a, &b)?;
+            out.value().assert_if_known(|o| **o == self.expected);
+            Ok(())
+        }
+    }
+
+    fn select_circuit(cond: u64, expected: u64) -> SelectCircuit {
+        SelectCircuit {
+            cond: Value::known(Fp::from(cond)),
+            a: Value::known(Fp::from(10)),
+            b: Value::known(Fp::from(20)),
+            expected: Fp::from(expected),
+        }
+    }
+
+    #[test]
+    fn select_a_when_cond_is_1() {
+        MockProver::run(4, &select_circuit(1, 10), vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn select_b_when_cond_is_0() {
+        MockProver::run(4, &select_circuit(0, 20), vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn select_non_boolean_cond_fails() {
+        // out = 2 * 10 - 20 = 0 satisfies the select constraint, but not the boolean one
+        let prover = MockProver::run(4, &select_circuit(2, 0), vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! EVM verifier of the KZG circuit, enabled by the `solidity` feature
+//! note: the contract reads a keccak transcript, so proofs for it come from `prove_fibonacci_evm`,
+//! not the blake2b `prove_fibonacci_kzg`
+use halo2_axiom::{
+    halo2curves::bn256::{Bn256, Fq, Fr, G1Affine},
+    plonk::{create_proof, keygen_pk, keygen_vk, verify_proof, Circuit, Error, VerifyingKey},
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::{KZGCommitmentScheme, ParamsKZG},
+            multiopen::{ProverSHPLONK, VerifierSHPLONK},
+            strategy::SingleStrategy,
+        },
+    },
+    transcript::{TranscriptReadBuffer, TranscriptWriterBuffer},
+};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use rand_core::OsRng;
+use snark_verifier::{
+    loader::evm::{encode_calldata, EvmLoader},
+    pcs::kzg::{Bdfg21, KzgAs},
+    system::halo2::{compile, transcript::evm::EvmTranscript, Config},
+    verifier::{self, SnarkVerifier},
+};
+use std::rc::Rc;
+
+use crate::FiboCircuit;
+
+// SHPLONK, same multiopen as `prove_fibonacci_kzg`
+type PlonkVerifier = verifier::plonk::PlonkVerifier<KzgAs<Bn256, Bdfg21>>;
+
+/// deterministic KZG params for circuit size `k`, so a contract can be generated from `k` alone
+/// note: INSECURE, anyone can rebuild the toxic waste from the seed. fine for tests and local
+/// chains, a real deployment must use ceremony params with `generate_solidity_verifier_with_params`
+pub fn params_for_solidity(k: u32) -> ParamsKZG<Bn256> {
+    ParamsKZG::setup(k, ChaCha20Rng::seed_from_u64(k as u64))
+}
+
+/// solidity source of a contract verifying the `n`-th term proof, see `params_for_solidity`
+pub fn generate_solidity_verifier(k: u32, n: usize) -> String {
+    generate_solidity_verifier_with_params(&params_for_solidity(k), n)
+}
+
+/// same as `generate_solidity_verifier`, but the vk comes from the given params
+pub fn generate_solidity_verifier_with_params(params: &ParamsKZG<Bn256>, n: usize) -> String {
+    let circuit = FiboCircuit::<Fr>::empty(n);
+    let vk = keygen_vk(params, &circuit).expect("keygen of FiboCircuit doesn't need witnesses");
+    solidity_code(params, &vk)
+}
+
+// one instance column with the output
+fn solidity_code(params: &ParamsKZG<Bn256>, vk: &VerifyingKey<G1Affine>) -> String {
+    let protocol = compile(params, vk, Config::kzg().with_num_instance(vec![1]));
+    let vk = (params.get_g()[0], params.g2(), params.s_g2()).into();
+
+    // running the verifier on the EVM loader records it as solidity
+    let loader = EvmLoader::new::<Fq, Fr>();
+    let protocol = protocol.loaded(&loader);
+    let mut transcript = EvmTranscript::<_, Rc<EvmLoader>, _, _>::new(&loader);
+    let instances = transcript.load_instances(vec![1]);
+    let proof = PlonkVerifier::read_proof(&vk, &protocol, &instances, &mut transcript).unwrap();
+    PlonkVerifier::verify(&vk, &protocol, &instances, &proof).unwrap();
+    loader.solidity_code()
+}
+
+/// `prove_fibonacci_kzg` with the keccak transcript the contract reads
+pub fn prove_fibonacci_evm(
+    params: &ParamsKZG<Bn256>,
+    circuit: FiboCircuit<Fr>,
+    public_inputs: &[&[Fr]],
+) -> Result<Vec<u8>, Error> {
+    let vk = keygen_vk(params, &circuit.without_witnesses())?;
+    let pk = keygen_pk(params, vk, &circuit.without_witnesses())?;
+
+    let mut transcript = TranscriptWriterBuffer::<_, G1Affine, _>::init(vec![]);
+    create_proof::<
+        KZGCommitmentScheme<Bn256>,
+        ProverSHPLONK<'_, Bn256>,
+        _,
+        _,
+        EvmTranscript<_, _, _, _>,
+        _,
+    >(
+        params,
+        &pk,
+        &[circuit],
+        &[public_inputs],
+        OsRng,
+        &mut transcript,
+    )?;
+    Ok(transcript.finalize())
+}
+
+/// `verify_fibonacci_kzg` for proofs of `prove_fibonacci_evm`, without the EVM
+pub fn verify_fibonacci_evm(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<G1Affine>,
+    public_inputs: &[&[Fr]],
+    proof: &[u8],
+) -> Result<(), Error> {
+    let strategy = SingleStrategy::new(params);
+    let mut transcript = TranscriptReadBuffer::<_, G1Affine, _>::init(proof);
+    verify_proof::<
+        KZGCommitmentScheme<Bn256>,
+        VerifierSHPLONK<'_, Bn256>,
+        _,
+        EvmTranscript<_, _, _, _>,
+        _,
+    >(params, vk, strategy, &[public_inputs], &mut transcript)
+}
+
+/// calldata of the verifier contract: the public inputs as 32 byte words, column by column,
+/// then the proof
+pub fn generate_calldata(proof: &[u8], public_inputs: &[&[Fr]]) -> Vec<u8> {
+    let instances: Vec<_> = public_inputs.iter().map(|column| column.to_vec()).collect();
+    encode_calldata(&instances, proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snark_verifier::loader::evm::{compile_solidity, deploy_and_call};
+    use std::marker::PhantomData;
+
+    fn fibo_10th_evm_proof(params: &ParamsKZG<Bn256>) -> Vec<u8> {
+        let circuit = FiboCircuit {
+            a: Some(Fr::from(1)),
+            b: Some(Fr::from(1)),
+            n: 10,
+            output: None,
+            planner: PhantomData,
+        };
+        prove_fibonacci_evm(params, circuit, &[&[Fr::from(55)]]).unwrap()
+    }
+
+    #[test]
+    fn fibo_solidity_source() {
+        let code = generate_solidity_verifier(4, 10);
+        assert!(code.contains("pragma solidity"));
+        assert!(code.contains("contract"));
+        // the setup is deterministic, so is the contract
+        assert_eq!(code, generate_solidity_verifier(4, 10));
+    }
+
+    #[test]
+    fn fibo_evm_proof_verifies_natively() {
+        let params = params_for_solidity(4);
+        let circuit = FiboCircuit::<Fr>::empty(10);
+        let vk = keygen_vk(&params, &circuit).unwrap();
+
+        let proof = fibo_10th_evm_proof(&params);
+        assert!(verify_fibonacci_evm(&params, &vk, &[&[Fr::from(55)]], &proof).is_ok());
+        assert!(verify_fibonacci_evm(&params, &vk, &[&[Fr::from(56)]], &proof).is_err());
+
+        let calldata = generate_calldata(&proof, &[&[Fr::from(55)]]);
+        assert_eq!(calldata.len(), 32 + proof.len());
+        assert_eq!(calldata[31], 55);
+    }
+
+    #[test]
+    #[ignore = "needs solc on PATH"]
+    fn fibo_solidity_verifier_on_evm() {
+        let params = params_for_solidity(4);
+        let bytecode = compile_solidity(&generate_solidity_verifier_with_params(&params, 10));
+        let proof = fibo_10th_evm_proof(&params);
+
+        let calldata = generate_calldata(&proof, &[&[Fr::from(55)]]);
+        assert!(deploy_and_call(bytecode.clone(), calldata).is_ok());
+
+        // wrong public output, then a flipped proof byte
+        let calldata = generate_calldata(&proof, &[&[Fr::from(56)]]);
+        assert!(deploy_and_call(bytecode.clone(), calldata).is_err());
+        let mut calldata = generate_calldata(&proof, &[&[Fr::from(55)]]);
+        let last = calldata.len() - 1;
+        calldata[last] ^= 1;
+        assert!(deploy_and_call(bytecode, calldata).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use halo2_proofs::{
+    pasta::Fp,
+    plonk::{keygen_vk, Circuit},
+};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    auto_k, nth_fibonacci, params_for, prove_fibonacci_with_params, verify_fibonacci_with_params,
+    FiboCircuit, FiboError, FiboProof, FiboProofBundle, MAX_N,
+};
+
+/// prove the `n`-th term of the sequence from (a, b) in the browser
+/// returns `FiboProofBundle::to_bytes`, so `verify_wasm` can rebuild the vk
+/// note: params are generated in memory, no file cache. returns an empty blob if proving fails
+#[wasm_bindgen]
+pub fn prove_wasm(a: u64, b: u64, n: u32) -> Vec<u8> {
+    prove_blob(a, b, n as usize).unwrap_or_default()
+}
+
+/// verify a blob of `prove_wasm` with `expected` as the public output
+#[wasm_bindgen]
+pub fn verify_wasm(proof: &[u8], expected: u64) -> bool {
+    verify_blob(proof, expected).is_ok()
+}
+
+fn prove_blob(a: u64, b: u64, n: usize) -> Result<Vec<u8>, FiboError> {
+    let (a, b) = (Fp::from(a), Fp::from(b));
+    let circuit = FiboCircuit::builder().seed(a, b).length(n).build()?;
+    if n > MAX_N {
+        return Err(FiboError::InvalidLength {
+            expected: MAX_N,
+            got: n,
+        });
+    }
+    let k = auto_k(n);
+
+    let public_inputs = vec![nth_fibonacci(n, a, b)];
+    let proof = prove_fibonacci_with_params(&params_for(k), circuit, &public_inputs)?;
+
+    let bundle = FiboProofBundle {
+        n,
+        proof: FiboProof {
+            public_inputs,
+            proof,
+        },
+    };
+    Ok(bundle.to_bytes())
+}
+
+fn verify_blob(bytes: &[u8], expected: u64) -> Result<(), FiboError> {
+    let FiboProofBundle { n, proof } = FiboProofBundle::from_bytes(bytes)?;
+    // a blob can ask for any n, don't generate params beyond `MAX_K`
+    if n > MAX_N {
+        return Err(FiboError::InvalidLength {
+            expected: MAX_N,
+            got: n,
+        });
+    }
+    let k = auto_k(n);
+
+    // the vk only depends on n
+    let params = params_for(k);
+    let circuit = FiboCircuit::<Fp>::empty(n);
+    let vk = keygen_vk(&params, &circuit.without_witnesses())?;
+    // against `expected`, not the inputs in the blob
+    verify_fibonacci_with_params(&params, &vk, &[&[Fp::from(expected)]], &proof.proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    // native with `cargo test --features wasm`, headless under node with
+    // `wasm-pack test --node --no-default-features --features wasm`
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn wasm_proof_roundtrip() {
+        let proof = prove_wasm(1, 1, 10);
+        assert!(!proof.is_empty());
+        assert!(verify_wasm(&proof, 55));
+        assert!(!verify_wasm(&proof, 56));
+        assert!(!verify_wasm(&proof[..3], 55));
+        // too short to prove
+        assert!(prove_wasm(1, 1, 1).is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+use halo2_proofs::{arithmetic::Field, circuit::*, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// config of `FiboWideChip`, `PER_ROW + 2` advice columns
+#[derive(Clone, Debug)]
+pub struct FiboWideConfig {
+    pub advice: Vec<Column<Advice>>,
+    pub selector: Selector,
+    /// chains a row to the next one, see `FiboConfig::next_selector`
+    pub next_selector: Selector,
+    pub instance: Column<Instance>,
+}
+
+/// `FiboChip` with `PER_ROW` terms per row: each row holds two terms and the `PER_ROW`
+/// after them, so the sequence takes `(n - 2) / PER_ROW` rows (rounded up) instead of `n - 2`
+/// note: columns are traded for rows, every column adds a commitment to the proof
+pub struct FiboWideChip<F: Field, const PER_ROW: usize> {
+    config: FiboWideConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field, const PER_ROW: usize> FiboWideChip<F, PER_ROW> {
+    pub fn construct(config: FiboWideConfig) -> Self {
+        FiboWideChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> FiboWideConfig {
+        assert!(PER_ROW > 0, "a row needs at least one new term");
+        let advice: Vec<_> = (0..PER_ROW + 2).map(|_| meta.advice_column()).collect();
+        let selector = meta.selector();
+        let next_selector = meta.selector();
+        let instance = meta.instance_column();
+
+        for &column in &advice {
+            meta.enable_equality(column);
+        }
+        meta.enable_equality(instance);
+
+        meta.create_gate("wide add", |meta| {
+            // | t_0 | t_1 |    t_2    | ... | t_(PER_ROW + 1) | selector |
+            // |  a  |  b  |   a + b   | ... |                 |     s    |
+            let s = meta.query_selector(selector);
+            let t: Vec<_> = advice
+                .iter()
+                .map(|&column| meta.query_advice(column, Rotation::cur()))
+                .collect();
+            t.windows(3)
+                .map(|w| s.clone() * (w[0].clone() + w[1].clone() - w[2].clone()))
+                .collect::<Vec<_>>()
+        });
+
+        meta.create_gate("wide next row", |meta| {
+            // |        t_0        |         t_1         | ... | t_PER_ROW | t_(PER_ROW + 1) | next_selector |
+            // |                   |                     | ... |     y     |        z        |       s       |
+            // |         y         |          z          | ... |           |                 |               |
+            let s = meta.query_selector(next_selector);
+            let y = meta.query_advice(advice[PER_ROW], Rotation::cur());
+            let z = meta.query_advice(advice[PER_ROW + 1], Rotation::cur());
+            let a_next = meta.query_advice(advice[0], Rotation::next());
+            let b_next = meta.query_advice(advice[1], Rotation::next());
+            vec![s.clone() * (a_next - y), s * (b_next - z)]
+        });
+
+        FiboWideConfig {
+            advice,
+            selector,
+            next_selector,
+            instance,
+        }
+    }
+
+    /// same as `FiboChip::assign_sequence_single_region`, `PER_ROW` terms per row
+    pub fn assign_sequence_single_region(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Option<F>,
+        b: Option<F>,
+        n: usize,
+    ) -> Result<Vec<ACell<F>>, Error> {
+        // first row holds terms 1..=PER_ROW + 2, every next row adds PER_ROW
+        let rows = n.saturating_sub(2).div_ceil(PER_ROW).max(1);
+        layouter.assign_region(
+            || "wide sequence",
+            |mut region| {
+                let mut terms = [a, b].map(|v| v.map_or(Value::unknown(), Value::known));
+                let mut cells = Vec::with_capacity(rows * PER_ROW + 2);
+
+                for row in 0..rows {
+                    self.config.selector.enable(&mut region, row)?;
+                    if row + 1 < rows {
+                        self.config.next_selector.enable(&mut region, row)?;
+                    }
+
+                    let mut row_values = terms.to_vec();
+                    for i in 0..PER_ROW {
+                        row_values.push(row_values[i] + row_values[i + 1]);
+                    }
+                    for (i, (&column, &value)) in
+                        self.config.advice.iter().zip(&row_values).enumerate()
+                    {
+                        let cell = region.assign_advice(|| "term", column, row, || value)?;
+                        // the first two of the next rows are already in the previous one
+                        if row == 0 || i >= 2 {
+                            cells.push(ACell(cell));
+                        }
+                    }
+                    terms = [row_values[PER_ROW], row_values[PER_ROW + 1]];
+                }
+
+                cells.truncate(n);
+                Ok(cells)
+            },
+        )
+    }
+
+    /// constrain the cell to be equal to the `row`-th value of the instance column
+    pub fn expose_public(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cell: &ACell<F>,
+        row: usize,
+    ) -> Result<(), Error> {
+        layouter.constrain_instance(cell.cell(), self.config.instance, row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cost_rows, fibonacci_cost, nth_fibonacci};
+    use halo2_proofs::{
+        dev::{CircuitCost, MockProver},
+        pasta::{Eq, Fp},
+    };
+
+    // the `n`-th term from (1, 1) at `instance[0]`, like `FiboCircuit`
+    #[derive(Debug)]
+    struct FiboWideCircuit<const PER_ROW: usize>(usize);
+
+    impl<const PER_ROW: usize> Circuit<Fp> for FiboWideCircuit<PER_ROW> {
+        type Config = FiboWideConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(self.0)
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            FiboWideChip::<Fp, PER_ROW>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = FiboWideChip::<Fp, PER_ROW>::construct(config);
+            let cells = chip.assign_sequence_single_region(
+                layouter.namespace(|| "sequence"),
+                Some(Fp::one()),
+                Some(Fp::one()),
+                self.0,
+            )?;
+            assert_eq!(cells.len(), self.0);
+            for (i, cell) in cells.iter().enumerate() {
+                let expected = nth_fibonacci(i + 1, Fp::one(), Fp::one());
+                cell.value().assert_if_known(|v| **v == expected);
+            }
+            chip.expose_public(layouter.namespace(|| "out"), cells.last().unwrap(), 0)
+        }
+    }
+
+    #[test]
+    fn fibo_wide_matches_reference() {
+        // partial last row, full last row, and a single row
+        for n in [2, 3, 6, 10, 11, 100] {
+            let expected = nth_fibonacci(n, Fp::one(), Fp::one());
+            MockProver::run(6, &FiboWideCircuit::<4>(n), vec![vec![expected]])
+                .unwrap()
+                .assert_satisfied();
+        }
+
+        let wrong = nth_fibonacci(100, Fp::one(), Fp::one()) + Fp::one();
+        let prover = MockProver::run(6, &FiboWideCircuit::<4>(100), vec![vec![wrong]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn fibo_wide_fewer_rows() {
+        // base chip: 98 rows, wide: 25 rows
+        let base = fibonacci_cost(7, 100);
+        let wide = CircuitCost::<Eq, _>::measure(7, &FiboWideCircuit::<4>(100));
+        assert_eq!(cost_rows(&base), 98);
+        assert_eq!(cost_rows(&wide), 25);
+    }
+}
@@ -0,0 +1,203 @@
+use halo2_proofs::{circuit::*, pasta::group::ff::PrimeField, plonk::*, poly::Rotation};
+use std::marker::PhantomData;
+
+use crate::ACell;
+
+/// config of the xor lookup: a row of (a, b, a ^ b) and the table of every such row
+#[derive(Clone, Debug)]
+pub struct XorConfig {
+    pub advice: [Column<Advice>; 3],
+    pub selector: Selector,
+    /// lhs, rhs and result
+    pub table: [TableColumn; 3],
+}
+
+/// `a ^ b` of `BITS`-bit values by a lookup into the full xor table
+/// note: the table has `2^(2 * BITS)` rows, so `k` must be above `2 * BITS`
+pub struct XorChip<F: PrimeField, const BITS: usize> {
+    config: XorConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: PrimeField, const BITS: usize> XorChip<F, BITS> {
+    pub fn construct(config: XorConfig) -> Self {
+        XorChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> XorConfig {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let selector = meta.complex_selector();
+        let table = [(); 3].map(|_| meta.lookup_table_column());
+
+        for column in advice {
+            meta.enable_equality(column);
+        }
+
+        meta.lookup(|meta| {
+            // | col_a | col_b | col_c | selector | lhs | rhs | result |
+            // |   a   |   b   | a ^ b |     s    |  l  |  r  | l ^ r  |
+            let s = meta.query_selector(selector);
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+
+            // rows without the selector look up (0, 0, 0), which is in the table
+            vec![
+                (s.clone() * a, table[0]),
+                (s.clone() * b, table[1]),
+                (s * c, table[2]),
+            ]
+        });
+
+        XorConfig {
+            advice,
+            selector,
+            table,
+        }
+    }
+
+    /// fill the table with every (l, r, l ^ r), once per circuit
+    pub fn load_table(&self, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+        layouter.assign_table(
+            || "xor table",
+            |mut table| {
+                let mut row = 0;
+                for l in 0..1u64 << BITS {
+                    for r in 0..1u64 << BITS {
+                        for (column, value) in self.config.table.into_iter().zip([l, r, l ^ r]) {
+                            table.assign_cell(
+                                || "xor",
+                                column,
+                                row,
+                                || Value::known(F::from(value)),
+                            )?;
+                        }
+                        row += 1;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// assign a, b and a ^ b in one row, returning the result cell
+    pub fn xor(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: Value<F>,
+        b: Value<F>,
+    ) -> Result<ACell<F>, Error> {
+        layouter.assign_region(
+            || "xor",
+            |mut region| {
+                self.config.selector.enable(&mut region, 0)?;
+
+                region.assign_advice(|| "a", self.config.advice[0], 0, || a)?;
+                region.assign_advice(|| "b", self.config.advice[1], 0, || b)?;
+                // note: an input wider than BITS has no row in the table, whatever the result
+                let c = a.zip(b).map(|(a, b)| F::from(low_u64(&a) ^ low_u64(&b)));
+                region
+                    .assign_advice(|| "c", self.config.advice[2], 0, || c)
+                    .map(ACell)
+            },
+        )
+    }
+}
+
+// low 64 bits of a little endian repr
+fn low_u64<F: PrimeField>(value: &F) -> u64 {
+    let repr = value.to_repr();
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&repr.as_ref()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{
+        dev::{MockProver, VerifyFailure},
+        pasta::Fp,
+    };
+
+    struct XorCircuit<const BITS: usize> {
+        a: Value<Fp>,
+        b: Value<Fp>,
+        // assign this as the result instead of a ^ b, to check the lookup rejects it
+        bad_c: Option<Fp>,
+    }
+
+    impl<const BITS: usize> Circuit<Fp> for XorCircuit<BITS> {
+        type Config = XorConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self {
+                a: Value::unknown(),
+                b: Value::unknown(),
+                bad_c: None,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            XorChip::<Fp, BITS>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fp>,
+        ) -> Result<(), Error> {
+            let chip = XorChip::<Fp, BITS>::construct(config.clone());
+            chip.load_table(layouter.namespace(|| "table"))?;
+
+            let Some(bad_c) = self.bad_c else {
+                let c = chip.xor(layouter.namespace(|| "xor"), self.a, self.b)?;
+                c.value().assert_if_known(|c| **c == Fp::from(6));
+                return Ok(());
+            };
+
+            layouter.assign_region(
+                || "bad xor",
+                |mut region| {
+                    config.selector.enable(&mut region, 0)?;
+                    region.assign_advice(|| "a", config.advice[0], 0, || self.a)?;
+                    region.assign_advice(|| "b", config.advice[1], 0, || self.b)?;
+                    region.assign_advice(|| "c", config.advice[2], 0, || Value::known(bad_c))?;
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn xor_5_3() {
+        let circuit = XorCircuit::<3> {
+            a: Value::known(Fp::from(5)),
+            b: Value::known(Fp::from(3)),
+            bad_c: None,
+        };
+        // 2^6 table rows + 6 reserved
+        MockProver::run(7, &circuit, vec![])
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    #[test]
+    fn xor_wrong_result_fails() {
+        // 5 | 3, a row of the or table
+        let circuit = XorCircuit::<3> {
+            a: Value::known(Fp::from(5)),
+            b: Value::known(Fp::from(3)),
+            bad_c: Some(Fp::from(7)),
+        };
+        let failures = MockProver::run(7, &circuit, vec![])
+            .unwrap()
+            .verify()
+            .unwrap_err();
+        assert!(failures
+            .iter()
+            .all(|f| matches!(f, VerifyFailure::Lookup { .. })));
+    }
+}
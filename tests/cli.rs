@@ -0,0 +1,123 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+fn fibo(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_fibo"));
+    command.args(args);
+    command
+}
+
+#[test]
+fn cli_prove_then_verify() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = dir.path().join("cache");
+    let proof = dir.path().join("proof.bin");
+    let (cache, proof) = (cache.to_str().unwrap(), proof.to_str().unwrap());
+
+    let status = fibo(&["prove", "--n", "10", "--out", proof, "--cache-dir", cache])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(dir.path().join("cache/params-k4.bin").exists());
+
+    let verify = |expected| {
+        fibo(&[
+            "verify",
+            "--proof",
+            proof,
+            "--cache-dir",
+            cache,
+            "--expected",
+            expected,
+        ])
+        .output()
+        .unwrap()
+    };
+    assert!(verify("55").status.success());
+    assert!(verify("0x37").status.success());
+    // wrong output is a non-zero exit
+    let wrong = verify("56");
+    assert_eq!(wrong.status.code(), Some(1));
+    assert!(!wrong.stderr.is_empty());
+}
+
+#[test]
+fn cli_prove_custom_seed() {
+    let dir = tempfile::tempdir().unwrap();
+    let proof = dir.path().join("proof.bin");
+    let proof = proof.to_str().unwrap();
+    let cache = dir.path().to_str().unwrap();
+
+    // 2, 3, 5, 8, 13
+    let status = fibo(&[
+        "prove",
+        "--a",
+        "2",
+        "--b",
+        "3",
+        "--n",
+        "5",
+        "--out",
+        proof,
+        "--cache-dir",
+        cache,
+    ])
+    .status()
+    .unwrap();
+    assert!(status.success());
+    let status = fibo(&[
+        "verify",
+        "--proof",
+        proof,
+        "--expected",
+        "13",
+        "--cache-dir",
+        cache,
+    ])
+    .status()
+    .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn cli_verify_rejects_huge_n() {
+    let dir = tempfile::tempdir().unwrap();
+    let proof = dir.path().join("proof.bin");
+    let cache = dir.path().join("cache");
+    let (proof_path, cache) = (proof.to_str().unwrap(), cache.to_str().unwrap());
+
+    let status = fibo(&[
+        "prove",
+        "--n",
+        "10",
+        "--out",
+        proof_path,
+        "--cache-dir",
+        cache,
+    ])
+    .status()
+    .unwrap();
+    assert!(status.success());
+
+    // the bundle starts with n, claim the largest one
+    let mut bytes = std::fs::read(&proof).unwrap();
+    bytes[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+    std::fs::write(&proof, bytes).unwrap();
+
+    let output = fibo(&[
+        "verify",
+        "--proof",
+        proof_path,
+        "--expected",
+        "55",
+        "--cache-dir",
+        cache,
+    ])
+    .output()
+    .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid length"));
+    // no params were generated for it
+    assert_eq!(std::fs::read_dir(cache).unwrap().count(), 1);
+}
@@ -0,0 +1,64 @@
+//! the add gate alone, on a single row with (a, b, c) chosen directly instead of by the chip
+use fibonacci::{FiboChip, FiboConfig};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::{MockProver, VerifyFailure},
+    pasta::Fp,
+    plonk::{Circuit, ConstraintSystem, Error},
+};
+
+struct AddRowCircuit([u64; 3]);
+
+impl Circuit<Fp> for AddRowCircuit {
+    type Config = FiboConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        AddRowCircuit(self.0)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        FiboChip::configure_standalone(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "add row",
+            |mut region| {
+                config.selector.enable(&mut region, 0)?;
+                for (column, value) in config.advice.into_iter().zip(self.0) {
+                    region.assign_advice(|| "cell", column, 0, || Value::known(Fp::from(value)))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+fn verify(row: [u64; 3]) -> Result<(), Vec<VerifyFailure>> {
+    // the instance column is unused
+    MockProver::run(4, &AddRowCircuit(row), vec![vec![]])
+        .unwrap()
+        .verify()
+}
+
+#[test]
+fn gate_add_satisfied() {
+    assert_eq!(verify([1, 1, 2]), Ok(()));
+    assert_eq!(verify([0, 0, 0]), Ok(()));
+}
+
+#[test]
+fn gate_add_wrong_sum() {
+    let failures = verify([1, 1, 3]).unwrap_err();
+    assert_eq!(failures.len(), 1);
+    assert!(matches!(
+        &failures[0],
+        VerifyFailure::ConstraintNotSatisfied { constraint, .. }
+            if constraint.to_string().contains("'add'")
+    ));
+}